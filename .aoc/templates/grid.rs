@@ -0,0 +1,33 @@
+{{header}}{{imports}}advent_of_code::solution!({{day}});
+
+/// The input as rows of bytes, indexed `grid[y][x]`.
+fn parse_grid(input: &str) -> Vec<Vec<u8>> {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}
+
+pub fn part_one(input: &str) -> Option<{{part_one_type}}> {
+    let _grid = parse_grid(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{part_two_type}}> {
+    let _grid = parse_grid(input);
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use advent_of_code::bitset::BitGrid;
+use advent_of_code::generate::{day03, day10};
+use advent_of_code::prop::Rng;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Day 10: remove the loop tiles from the valid positions, then look up every tile, on a
+/// full-size maze.
+fn visited_grid(c: &mut Criterion) {
+    let maze = day10::input(&mut Rng::new(2023), 139, 139, 139 * 139 / 2);
+    let width = maze.input.lines().next().unwrap().len();
+    let height = maze.input.lines().count();
+    let tiles = || (0..height).flat_map(|y| (0..width).map(move |x| (x, y)));
+
+    let mut group = c.benchmark_group("visited grid");
+    group.bench_function("BitGrid", |b| {
+        b.iter(|| {
+            let mut valid = BitGrid::full(width, height);
+            for &(x, y) in black_box(&maze.loop_tiles) {
+                valid.remove(x, y);
+            }
+            tiles().filter(|&(x, y)| valid.contains(x, y)).count()
+        })
+    });
+    group.bench_function("Vec<Vec<bool>>", |b| {
+        b.iter(|| {
+            let mut valid = vec![vec![true; width]; height];
+            for &(x, y) in black_box(&maze.loop_tiles) {
+                valid[y][x] = false;
+            }
+            tiles().filter(|&(x, y)| valid[y][x]).count()
+        })
+    });
+    group.finish();
+}
+
+/// The row of each number of a day 3 schematic, with the gears seen around each of its digits
+/// in the order day 3 visits them, duplicates included.
+fn gears_seen(input: &str) -> Vec<(usize, Vec<(usize, usize)>)> {
+    let grid = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+    let mut numbers = vec![];
+    for (y, row) in grid.iter().enumerate() {
+        let mut seen = vec![];
+        for x in 0..=row.len() {
+            if x < row.len() && row[x].is_ascii_digit() {
+                let around = (y.saturating_sub(1)..=y + 1)
+                    .flat_map(|ny| (x.saturating_sub(1)..=x + 1).map(move |nx| (nx, ny)));
+                seen.extend(
+                    around.filter(|&(nx, ny)| {
+                        grid.get(ny).and_then(|row| row.get(nx)) == Some(&b'*')
+                    }),
+                );
+            } else if x > 0 && row[x - 1].is_ascii_digit() {
+                numbers.push((y, std::mem::take(&mut seen)));
+            }
+        }
+    }
+    numbers
+}
+
+/// Day 3: collect the distinct gears around each number, then clear them for the next one, on
+/// a full-size schematic.
+fn seen_positions(c: &mut Criterion) {
+    let numbers = gears_seen(&day03::input(&mut Rng::new(2023), 140, 140));
+
+    let mut group = c.benchmark_group("seen positions");
+    group.bench_function("BitGrid", |b| {
+        b.iter(|| {
+            let mut seen = BitGrid::new(140, 3);
+            let mut sum = 0;
+            for (row, gears) in black_box(&numbers) {
+                for &(x, y) in gears {
+                    seen.insert(x, y + 1 - row);
+                }
+                sum += seen.iter().map(|(x, y)| x + y + row - 1).sum::<usize>();
+                seen.clear();
+            }
+            sum
+        })
+    });
+    group.bench_function("HashSet<(usize, usize)>", |b| {
+        b.iter(|| {
+            let mut seen = HashSet::new();
+            let mut sum = 0;
+            for (_, gears) in black_box(&numbers) {
+                seen.extend(gears.iter().copied());
+                sum += seen.iter().map(|(x, y)| x + y).sum::<usize>();
+                seen.clear();
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, visited_grid, seen_positions);
+criterion_main!(benches);
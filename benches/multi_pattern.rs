@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use advent_of_code::multi_pattern::MultiMatcher;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const DIGITS: [&str; 9] = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// 1000 lines shaped like a day 1 input, e.g. `xsevenjkl5onetwonex`.
+fn sample_input() -> String {
+    let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+    let mut next = move |n: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % n) as usize
+    };
+    (0..1000)
+        .map(|_| {
+            (0..8)
+                .map(|_| match next(3) {
+                    0 => WORDS[next(9)].to_string(),
+                    1 => DIGITS[next(9)].to_string(),
+                    _ => ((b'a' + next(26) as u8) as char).to_string(),
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 1's previous approach: search every pattern separately and order the hits in a map.
+fn match_indices(line: &str) -> u32 {
+    let nums = WORDS.iter().zip(DIGITS).collect::<BTreeMap<_, _>>();
+    let mut ordering = BTreeMap::new();
+    for (&&word, digit) in &nums {
+        for pattern in [word, digit] {
+            for (i, _) in line.match_indices(pattern) {
+                ordering.insert(i, digit.parse::<u32>().unwrap());
+            }
+        }
+    }
+    let first = ordering.values().next().copied().unwrap_or(0);
+    let last = ordering.values().last().copied().unwrap_or(0);
+    10 * first + last
+}
+
+fn digit_words(c: &mut Criterion) {
+    let input = sample_input();
+    let matcher = MultiMatcher::new(
+        WORDS
+            .into_iter()
+            .zip(1..)
+            .chain(DIGITS.into_iter().zip(1..)),
+    );
+
+    let mut group = c.benchmark_group("day 1 digit words");
+    group.bench_function("MultiMatcher::first_and_last", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .filter_map(|line| matcher.first_and_last(line))
+                .map(|(first, last)| 10 * first + last)
+                .sum::<u32>()
+        })
+    });
+    group.bench_function("match_indices + BTreeMap", |b| {
+        b.iter(|| black_box(&input).lines().map(match_indices).sum::<u32>())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, digit_words);
+criterion_main!(benches);
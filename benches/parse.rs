@@ -0,0 +1,40 @@
+use advent_of_code::parse;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn sample_line() -> String {
+    (0..64u64)
+        .map(|i| (i * 2_654_435_761 % 1_000_000_007).to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn single_int(c: &mut Criterion) {
+    let mut group = c.benchmark_group("single int");
+    group.bench_function("parse::parse_int", |b| {
+        b.iter(|| parse::parse_int::<u64>(black_box("1234567890")))
+    });
+    group.bench_function("str::parse", |b| {
+        b.iter(|| black_box("1234567890").parse::<u64>().ok())
+    });
+    group.finish();
+}
+
+fn line_of_ints(c: &mut Criterion) {
+    let line = sample_line();
+    let mut group = c.benchmark_group("line of ints");
+    group.bench_function("parse::ints", |b| {
+        b.iter(|| parse::ints::<u64>(black_box(&line)).sum::<u64>())
+    });
+    group.bench_function("split_whitespace + str::parse", |b| {
+        b.iter(|| {
+            black_box(&line)
+                .split_whitespace()
+                .map(|n| n.parse::<u64>().unwrap())
+                .sum::<u64>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, single_int, line_of_ints);
+criterion_main!(benches);
@@ -1,4 +1,6 @@
-use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use advent_of_code::multi_pattern::MultiMatcher;
 
 advent_of_code::solution!(1);
 
@@ -35,51 +37,30 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(res)
 }
 
-fn extract_nums(input: &str) -> Option<u32> {
-    if input.is_empty() {
-        return None;
-    }
-    let nums_hash = BTreeMap::from([
-        ("one", "1"),
-        ("two", "2"),
-        ("three", "3"),
-        ("four", "4"),
-        ("five", "5"),
-        ("six", "6"),
-        ("seven", "7"),
-        ("eight", "8"),
-        ("nine", "9"),
-    ]);
+static DIGITS: LazyLock<MultiMatcher<u32>> = LazyLock::new(|| {
+    let words = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let digits = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+    MultiMatcher::new(
+        words
+            .into_iter()
+            .zip(1..)
+            .chain(digits.into_iter().zip(1..)),
+    )
+});
 
-    let mut ordering_map: BTreeMap<usize, u32> = BTreeMap::new();
-    for &ch_num in nums_hash.keys().chain(nums_hash.values()) {
-        input.match_indices(ch_num).for_each(|(id, _)| {
-            ordering_map.insert(
-                id,
-                nums_hash
-                    .get(ch_num)
-                    .unwrap_or(&ch_num)
-                    .parse()
-                    .expect("couldn't parse the number!"),
-            );
-        });
-    }
-    let ordered: Vec<_> = ordering_map.values().collect();
-
-    let mut res_val = 0;
-
-    if let Some(&&first) = ordered.first() {
-        res_val += 10 * first;
-    }
-    if let Some(&&last) = ordered.last() {
-        res_val += last;
-    }
-
-    Some(res_val)
+fn extract_nums(input: &str) -> Option<u32> {
+    let (first, last) = DIGITS.first_and_last(input)?;
+    Some(10 * first + last)
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::generate;
+    use advent_of_code::prop::{self, Rng};
+    use advent_of_code::prop_assert_eq;
+
     use super::*;
 
     #[test]
@@ -93,4 +74,49 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_part_two_overlapping_words() {
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        assert_eq!(result, Some(281));
+        assert_eq!(extract_nums("oneight"), Some(18));
+        assert_eq!(extract_nums("xyz"), None);
+    }
+
+    fn random_input(rng: &mut Rng, size: usize) -> String {
+        let lines = rng.range(1..=size + 1);
+        generate::day01::input(rng, lines, size / 4 + 2)
+    }
+
+    /// Tries every pattern at every position.
+    fn naive_calibration(line: &str, words: bool) -> Option<u32> {
+        let digits = (0..line.len())
+            .filter_map(|i| {
+                let rest = &line[i..];
+                let digit = rest.chars().next()?.to_digit(10);
+                let word = generate::day01::WORDS
+                    .iter()
+                    .position(|w| words && rest.starts_with(w));
+                digit.or(word.map(|w| w as u32 + 1))
+            })
+            .collect::<Vec<_>>();
+        Some(10 * digits.first()? + digits.last()?)
+    }
+
+    #[test]
+    fn test_against_naive() {
+        prop::check(prop::from_fn(random_input), |input| {
+            let naive = |words| {
+                input
+                    .lines()
+                    .filter_map(|l| naive_calibration(l, words))
+                    .sum()
+            };
+            prop_assert_eq!(part_one(input), Some(naive(false)));
+            prop_assert_eq!(part_two(input), Some(naive(true)));
+            Ok(())
+        });
+    }
 }
@@ -1,3 +1,6 @@
+use advent_of_code::parse;
+use advent_of_code::pattern::{Pattern, PatternError};
+
 advent_of_code::solution!(2);
 
 struct Game {
@@ -20,22 +23,22 @@ impl Game {
 }
 
 fn get_games(input: &str) -> Vec<Game> {
-    input
-        .lines()
-        .map(|line| {
-            let mut game = Game::new(0, 0, 0);
-            let (_game, data) = line.split_once(':').expect("error parsing input");
-            for data_chunk in data.split(';') {
-                for color_data in data_chunk.split(',') {
-                    let (num, color) = color_data.trim().split_once(' ').unwrap();
-                    let num = num.parse::<usize>().expect("couldn't parse a number");
-
-                    game.update_color(color, num);
+    let game = Pattern::new("Game {id}: {draws:list(\"; \")}").expect("valid pattern");
+    let draw = Pattern::new("{cubes:list(\", \")}").expect("valid pattern");
+    let cube = Pattern::new("{count} {color}").expect("valid pattern");
+
+    parse::lines(input)
+        .map(|line| -> Result<Game, PatternError> {
+            let mut game_data = Game::new(0, 0, 0);
+            for draw_data in game.captures(line)?.list_with("draws", &draw)? {
+                for cube_data in draw_data.list_with("cubes", &cube)? {
+                    game_data.update_color(cube_data.str("color")?, cube_data.get("count")?);
                 }
             }
-            game
+            Ok(game_data)
         })
-        .collect()
+        .collect::<Result<_, _>>()
+        .unwrap_or_else(|e| panic!("error parsing input: {e}"))
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -64,6 +67,10 @@ pub fn part_two(input: &str) -> Option<usize> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::generate;
+    use advent_of_code::prop::{self, Rng};
+    use advent_of_code::prop_assert_eq;
+
     use super::*;
 
     #[test]
@@ -77,4 +84,45 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2286));
     }
+
+    fn random_input(rng: &mut Rng, size: usize) -> String {
+        let games = rng.range(1..=size + 1);
+        generate::day02::input(rng, games, 6)
+    }
+
+    /// The largest count of each color per game, parsed with plain string splitting.
+    fn naive_maxima(input: &str) -> Vec<[usize; 3]> {
+        input
+            .lines()
+            .map(|line| {
+                let mut maxima = [0; 3];
+                let (_, draws) = line.split_once(": ").unwrap();
+                for cubes in draws.split(&[';', ',']) {
+                    let (count, color) = cubes.trim().split_once(' ').unwrap();
+                    let color = generate::day02::COLORS
+                        .iter()
+                        .position(|&c| c == color)
+                        .unwrap();
+                    maxima[color] = maxima[color].max(count.parse().unwrap());
+                }
+                maxima
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_against_naive() {
+        prop::check(prop::from_fn(random_input), |input| {
+            let maxima = naive_maxima(input);
+            let possible = (1..)
+                .zip(&maxima)
+                .filter(|(_, [r, g, b])| *r <= 12 && *g <= 13 && *b <= 14)
+                .map(|(id, _)| id)
+                .sum();
+            let power = maxima.iter().map(|[r, g, b]| r * g * b).sum();
+            prop_assert_eq!(part_one(input), Some(possible));
+            prop_assert_eq!(part_two(input), Some(power));
+            Ok(())
+        });
+    }
 }
@@ -1,4 +1,6 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+
+use advent_of_code::bitset::BitGrid;
 
 advent_of_code::solution!(3);
 
@@ -36,7 +38,6 @@ impl NumberData {
 pub fn part_one(input: &str) -> Option<u32> {
     let lines: Vec<Vec<_>> = input.lines().map(|line| line.chars().collect()).collect();
     let rows = lines.len();
-    let columns = lines[0].len();
 
     let mut numbers = Vec::new();
 
@@ -50,7 +51,7 @@ pub fn part_one(input: &str) -> Option<u32> {
                     let lower_x = x_id.saturating_sub(1);
                     let lower_y = y_id.saturating_sub(1);
                     let upper_y = if y_id < rows - 1 { y_id + 1 } else { y_id };
-                    let upper_x = if x_id < columns - 1 { x_id + 1 } else { x_id };
+                    let upper_x = x_id + 1;
 
                     for y in lower_y..=upper_y {
                         for x in lower_x..=upper_x {
@@ -68,7 +69,7 @@ pub fn part_one(input: &str) -> Option<u32> {
                 numbers.push(number);
                 number = NumberData::new();
             }
-            if x_id == columns - 1 && !number.is_empty() {
+            if x_id == line.len() - 1 && !number.is_empty() {
                 numbers.push(number);
                 number = NumberData::new();
             }
@@ -87,13 +88,15 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let lines: Vec<Vec<_>> = input.lines().map(|line| line.chars().collect()).collect();
     let rows = lines.len();
-    let columns = lines[0].len();
+    // lines may differ in length.
+    let width = lines.iter().map(Vec::len).max().unwrap_or(0);
 
     let mut gears_n_nums: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
 
     for (y_id, line) in input.lines().enumerate() {
         let mut number = NumberData::new();
-        let mut num_specific_g = HashSet::new();
+        // the gears around the current number, in the rows from `y_id - 1` to `y_id + 1`.
+        let mut num_specific_g = BitGrid::new(width, 3);
 
         for (x_id, ch) in line.chars().enumerate() {
             if ch.is_ascii_digit() {
@@ -103,14 +106,14 @@ pub fn part_two(input: &str) -> Option<u32> {
                     let lower_x = x_id.saturating_sub(1);
                     let lower_y = y_id.saturating_sub(1);
                     let upper_y = if y_id < rows - 1 { y_id + 1 } else { y_id };
-                    let upper_x = if x_id < columns - 1 { x_id + 1 } else { x_id };
+                    let upper_x = x_id + 1;
 
                     for y in lower_y..=upper_y {
                         for x in lower_x..=upper_x {
                             if let Some(line) = lines.get(y) {
                                 if let Some(&chr) = line.get(x) {
                                     if chr == '*' {
-                                        num_specific_g.insert((x, y));
+                                        num_specific_g.insert(x, y + 1 - y_id);
                                     }
                                 }
                             }
@@ -118,10 +121,10 @@ pub fn part_two(input: &str) -> Option<u32> {
                     }
                 }
             } else if !number.is_empty() {
-                clear_and_collect(&mut number, &mut num_specific_g, &mut gears_n_nums);
+                clear_and_collect(y_id, &mut number, &mut num_specific_g, &mut gears_n_nums);
             }
-            if x_id == columns - 1 && !number.is_empty() {
-                clear_and_collect(&mut number, &mut num_specific_g, &mut gears_n_nums);
+            if x_id == line.len() - 1 && !number.is_empty() {
+                clear_and_collect(y_id, &mut number, &mut num_specific_g, &mut gears_n_nums);
             }
         }
     }
@@ -136,17 +139,16 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn clear_and_collect(
+    y_id: usize,
     number: &mut NumberData,
-    num_specific_g: &mut HashSet<(usize, usize)>,
+    num_specific_g: &mut BitGrid,
     gears_n_nums: &mut HashMap<(usize, usize), Vec<usize>>,
 ) {
-    for gear in num_specific_g.iter() {
-        if !gears_n_nums.contains_key(gear) {
-            gears_n_nums.insert(*gear, Vec::new());
-        }
-        if let Some(entry) = gears_n_nums.get_mut(gear) {
-            entry.push(number.extract_num());
-        }
+    for (x, y) in num_specific_g.iter() {
+        gears_n_nums
+            .entry((x, y + y_id - 1))
+            .or_default()
+            .push(number.extract_num());
     }
     num_specific_g.clear();
     number.clear();
@@ -154,6 +156,11 @@ fn clear_and_collect(
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::generate;
+    use advent_of_code::prop::{self, Rng};
+    use advent_of_code::prop_assert_eq;
+    use itertools::Itertools;
+
     use super::*;
 
     #[test]
@@ -167,4 +174,72 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(467835));
     }
+
+    #[test]
+    fn test_ragged_lines() {
+        assert_eq!(part_one("1\n...4*5"), Some(9));
+        assert_eq!(part_two("1\n...4*5"), Some(20));
+    }
+
+    fn random_input(rng: &mut Rng, size: usize) -> String {
+        let (width, height) = (rng.range(1..=size / 2 + 3), rng.range(1..=size / 2 + 3));
+        generate::day03::input(rng, width, height)
+    }
+
+    /// Returns each number with the positions around it.
+    fn naive_numbers(grid: &[Vec<char>]) -> Vec<(u32, Vec<(usize, usize)>)> {
+        let mut numbers = Vec::new();
+        for (y, row) in grid.iter().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                let len = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+                if len == 0 {
+                    x += 1;
+                    continue;
+                }
+                let value = row[x..x + len].iter().collect::<String>().parse().unwrap();
+                let around = (y.saturating_sub(1)..=y + 1)
+                    .cartesian_product(x.saturating_sub(1)..=x + len)
+                    .filter(|&(ny, nx)| ny < grid.len() && nx < row.len())
+                    .map(|(ny, nx)| (nx, ny))
+                    .collect();
+                numbers.push((value, around));
+                x += len;
+            }
+        }
+        numbers
+    }
+
+    #[test]
+    fn test_against_naive() {
+        prop::check(prop::from_fn(random_input), |input| {
+            let grid = input.lines().map(|l| l.chars().collect_vec()).collect_vec();
+            let numbers = naive_numbers(&grid);
+            let is_symbol = |&(x, y): &(usize, usize)| {
+                let c: char = grid[y][x];
+                !c.is_ascii_digit() && c != '.'
+            };
+            let parts = numbers
+                .iter()
+                .filter(|(_, around)| around.iter().any(is_symbol))
+                .map(|(value, _)| value)
+                .sum();
+
+            let mut gears = HashMap::<_, Vec<u32>>::new();
+            for (value, around) in &numbers {
+                for &(x, y) in around.iter().filter(|&&(x, y)| grid[y][x] == '*') {
+                    gears.entry((x, y)).or_default().push(*value);
+                }
+            }
+            let ratios = gears
+                .values()
+                .filter(|values| values.len() == 2)
+                .map(|values| values[0] * values[1])
+                .sum();
+
+            prop_assert_eq!(part_one(input), Some(parts));
+            prop_assert_eq!(part_two(input), Some(ratios));
+            Ok(())
+        });
+    }
 }
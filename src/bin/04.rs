@@ -65,6 +65,12 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
+
+    use advent_of_code::generate;
+    use advent_of_code::prop::{self, Rng};
+    use advent_of_code::prop_assert_eq;
+
     use super::*;
 
     #[test]
@@ -78,4 +84,41 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(30));
     }
+
+    fn random_input(rng: &mut Rng, size: usize) -> String {
+        let cards = rng.range(1..=size / 8 + 2);
+        let (winning, own) = (rng.range(1..=10), rng.range(1..=25));
+        generate::day04::input(rng, cards, winning, own)
+    }
+
+    #[test]
+    fn test_against_naive() {
+        prop::check(prop::from_fn(random_input), |input| {
+            let matches = input
+                .lines()
+                .map(|line| {
+                    let (winning, mine) =
+                        line.split_once(": ").unwrap().1.split_once(" | ").unwrap();
+                    let mine = mine.split_whitespace().collect_vec();
+                    winning
+                        .split_whitespace()
+                        .filter(|n| mine.contains(n))
+                        .count()
+                })
+                .collect_vec();
+            let points = matches.iter().map(|&m| (1 << m) >> 1).sum();
+
+            // processes every single copy.
+            let mut queue = (0..matches.len()).collect::<VecDeque<_>>();
+            let mut copies = 0;
+            while let Some(card) = queue.pop_front() {
+                copies += 1;
+                queue.extend(card + 1..=card + matches[card]);
+            }
+
+            prop_assert_eq!(part_one(input), Some(points));
+            prop_assert_eq!(part_two(input), Some(copies));
+            Ok(())
+        });
+    }
 }
@@ -1,95 +1,220 @@
-use std::{
-    collections::{BTreeSet, VecDeque},
-    ops::Range,
-};
+use std::ops::Range;
 
+use advent_of_code::parse;
 use itertools::Itertools;
-use range_ext::intersect::{Intersect, IntersectionExt};
 use tracing::info;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, part_two: [ranges => part_two, bruteforce => part_two_bruteforce]);
 
-// In part one we have 'small' amount of data in SEEDS so it makes sense to iterate through each SEED.
-// Doing it in reverse takes a lot longer since we have a smaller pool to match to.
-// Part two BRUTEFORCE counterpart is at the bottom of the file
+// All mapping layers are composed into a single piecewise-linear map once, after which each
+// seed (part one) or seed range (part two) is a binary search away from its lowest location.
+// The original part two BRUTEFORCE is kept as the `bruteforce` variant further down, run it
+// with `cargo solve 5 --variant bruteforce` or compare both with `cargo compare 5`.
 pub fn part_one(input: &str) -> Option<u32> {
-    let seed_map: Vec<i64> = input
-        .lines()
-        .take(1)
-        .flat_map(|line| {
-            let (_, nums) = line.split_once(": ").expect("Should be splitable by ': '");
-            nums.split_whitespace().map(parse_to_i64)
-        })
-        .collect_vec();
+    let seeds: Vec<i64> = parse::ints(input.lines().next()?).collect_vec();
 
     let chunks = chunkify(input);
     info!(?chunks);
 
-    let res_map = proccess_seeds(seed_map, &chunks);
-    res_map.first().map(|n| n.to_owned() as u32)
+    let map = PiecewiseMap::compose(&chunks);
+    seeds
+        .into_iter()
+        .map(|seed| map.apply(seed))
+        .min()
+        .map(|n| n as u32)
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    // tracing_subscriber::fmt().with_target(false).init();
-    info!("ENTERED part_two");
+    let seed_ranges = parse::ints::<i64>(input.lines().next()?)
+        .tuples::<(_, _)>()
+        .map(|(seed_num, len)| seed_num..seed_num + len)
+        .collect_vec();
+    info!("Collected seed ranges - LEN: {}", seed_ranges.len());
 
-    let seed_map = input
-        .lines()
-        .take(1)
-        .flat_map(|line| {
-            let (_, nums) = line.split_once(": ").expect("Should be splitable by ': '");
-            nums.split_whitespace()
-                .map(parse_to_i64)
-                .tuples::<(_, _)>()
-                .map(|(seed_num, len)| seed_num..seed_num + len)
-        })
-        .collect::<VecDeque<_>>();
-    info!("Collected seed map - LEN: {}", seed_map.len());
+    let map = PiecewiseMap::compose(&chunkify(input));
+    info!("Composed map - PIECES: {}", map.pieces.len());
 
-    let chunks = chunkify(input);
-    info!("Chunkified - LEN: {}", chunks.len());
+    let lowest = seed_ranges
+        .iter()
+        .filter_map(|range| map.min_over(range.clone()))
+        .min();
+
+    debug_assert!(
+        lowest.is_none_or(|location| {
+            let seed = map.inverse().apply(location);
+            seed_ranges.iter().any(|range| range.contains(&seed))
+        }),
+        "reverse lookup of the lowest location should land on a seed"
+    );
+    lowest
+}
 
-    process_seed_map(seed_map, &chunks)
+/// A map `x -> x + offset` whose offset changes at sorted breakpoints.
+/// Each piece starts at its `start` and runs up to the next piece; the first starts at `i64::MIN`.
+#[derive(Clone, Debug, PartialEq)]
+struct PiecewiseMap {
+    pieces: Vec<Piece>,
 }
 
-fn process_seed_map(seed_map: VecDeque<Range<i64>>, chunks: &[DataChunk]) -> Option<i64> {
-    let mut seed_ranges = seed_map;
-    let mut op_ranges = VecDeque::new();
-
-    for chunk in chunks {
-        'seeds: while let Some(mut seed_r) = seed_ranges.pop_front() {
-            for (src_r, offset) in &chunk.data {
-                match seed_r.intersect_ext(src_r) {
-                    IntersectionExt::LessOverlap => {
-                        op_ranges.push_back(src_r.start + offset..seed_r.end + offset);
-                        seed_r = seed_r.start..src_r.start;
-                    }
-                    IntersectionExt::GreaterOverlap => {
-                        op_ranges.push_back(seed_r.start + offset..src_r.end + offset);
-                        seed_r = src_r.end..seed_r.end;
-                    }
-                    IntersectionExt::Within | IntersectionExt::Same => {
-                        op_ranges.push_back(seed_r.start + offset..seed_r.end + offset);
-                        continue 'seeds;
-                    }
-                    IntersectionExt::Over => {
-                        let under = seed_r.start..src_r.start;
-                        let middle = src_r.start + offset..src_r.end + offset;
-                        let over = src_r.end..seed_r.end;
-                        op_ranges.push_back(middle);
-                        seed_ranges.push_front(under);
-                        seed_ranges.push_front(over);
-                        continue 'seeds;
-                    }
-                    _ => {}
-                };
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Piece {
+    start: i64,
+    offset: i64,
+}
+
+impl PiecewiseMap {
+    fn identity() -> Self {
+        PiecewiseMap {
+            pieces: vec![Piece {
+                start: i64::MIN,
+                offset: 0,
+            }],
+        }
+    }
+
+    /// The map of a single layer: values in a source range are shifted, all others stay.
+    fn from_chunk(chunk: &DataChunk) -> Self {
+        let mut pieces = vec![];
+        let mut end = i64::MIN;
+        for (src, offset) in chunk.iter().sorted_by_key(|(src, _)| src.start) {
+            if src.start > end {
+                pieces.push(Piece {
+                    start: end,
+                    offset: 0,
+                });
+            }
+            pieces.push(Piece {
+                start: src.start,
+                offset: *offset,
+            });
+            end = src.end;
+        }
+        pieces.push(Piece {
+            start: end,
+            offset: 0,
+        });
+        Self::normalized(pieces)
+    }
+
+    /// Composes the layers, so that the result maps a seed straight to its location.
+    fn compose(chunks: &[DataChunk]) -> Self {
+        chunks.iter().fold(Self::identity(), |map, chunk| {
+            map.then(&Self::from_chunk(chunk))
+        })
+    }
+
+    fn end_of(&self, i: usize) -> i64 {
+        self.pieces.get(i + 1).map_or(i64::MAX, |p| p.start)
+    }
+
+    /// Index of the piece containing `x`.
+    fn piece_at(&self, x: i64) -> usize {
+        self.pieces.partition_point(|p| p.start <= x) - 1
+    }
+
+    fn apply(&self, x: i64) -> i64 {
+        x + self.pieces[self.piece_at(x)].offset
+    }
+
+    /// Returns the map `x -> next(self(x))`.
+    fn then(&self, next: &PiecewiseMap) -> Self {
+        let mut pieces = vec![];
+        for (i, piece) in self.pieces.iter().enumerate() {
+            let (start, end) = (piece.start, self.end_of(i));
+            let image_start = start.saturating_add(piece.offset);
+            let image_end = end.saturating_add(piece.offset);
+
+            // split the image of this piece wherever `next` changes its offset.
+            let mut j = next.piece_at(image_start);
+            while j < next.pieces.len() && next.pieces[j].start < image_end {
+                pieces.push(Piece {
+                    start: next.pieces[j]
+                        .start
+                        .max(image_start)
+                        .saturating_sub(piece.offset),
+                    offset: piece.offset + next.pieces[j].offset,
+                });
+                j += 1;
+            }
+        }
+        Self::normalized(pieces)
+    }
+
+    /// Returns the reverse lookup `location -> seed`; the map has to be a bijection.
+    fn inverse(&self) -> Self {
+        let pieces = self
+            .pieces
+            .iter()
+            .map(|p| Piece {
+                start: p.start.saturating_add(p.offset),
+                offset: -p.offset,
+            })
+            .sorted_by_key(|p| p.start)
+            .collect();
+        Self::normalized(pieces)
+    }
+
+    /// The lowest value the map takes on `range`. Every piece is increasing, so only the start
+    /// of `range` and of the pieces within it need to be checked.
+    fn min_over(&self, range: Range<i64>) -> Option<i64> {
+        if range.is_empty() {
+            return None;
+        }
+        let first = self.piece_at(range.start);
+        let last = self.piece_at(range.end - 1);
+        self.pieces[first..=last]
+            .iter()
+            .map(|p| p.start.max(range.start) + p.offset)
+            .min()
+    }
+
+    /// Drops empty pieces and merges neighbours with the same offset.
+    fn normalized(pieces: Vec<Piece>) -> Self {
+        let mut merged: Vec<Piece> = Vec::with_capacity(pieces.len());
+        for (i, piece) in pieces.iter().enumerate() {
+            let end = pieces.get(i + 1).map_or(i64::MAX, |p| p.start);
+            if piece.start >= end && i + 1 < pieces.len() {
+                continue;
+            }
+            match merged.last() {
+                Some(last) if last.offset == piece.offset => {}
+                _ => merged.push(*piece),
             }
-            op_ranges.push_back(seed_r);
         }
-        seed_ranges = op_ranges;
-        op_ranges = VecDeque::new();
+        PiecewiseMap { pieces: merged }
     }
-    seed_ranges.iter().map(|range| range.start).min()
+}
+
+// NOTE: This is the Bruteforce method used as the original solution.
+//
+// In part two we have enormous ranges of data in SEEDS so instead of processing each SEED it
+// makes sense to BRUTEFORCE in reverse and look for matches (even more so because of the huge
+// ranges, there is a big chance we will hit a match early).
+fn find_seed(chunks: &[DataChunk], seed_map: &[Range<i64>]) -> Option<i64> {
+    (0..i64::MAX).find(|&n| {
+        let mut num_op = n;
+        for chunk in chunks.iter().rev() {
+            if let Some((_, diff)) = chunk
+                .iter()
+                .find(|(src_range, diff)| src_range.contains(&(num_op - diff)))
+            {
+                num_op -= diff;
+            }
+        }
+        seed_map
+            .iter()
+            .any(|seed_range| seed_range.contains(&num_op))
+    })
+}
+
+pub fn part_two_bruteforce(input: &str) -> Option<i64> {
+    let seed_map = parse::ints::<i64>(input.lines().next()?)
+        .tuples::<(_, _)>()
+        .map(|(seed_num, len)| seed_num..seed_num + len)
+        .collect_vec();
+
+    let chunks = chunkify(input);
+    find_seed(&chunks, &seed_map)
 }
 
 #[derive(Clone, Debug)]
@@ -112,12 +237,6 @@ impl DataChunk {
     }
 }
 
-fn parse_to_i64(input: &str) -> i64 {
-    input
-        .parse::<i64>()
-        .expect("Number should be parsable to i64")
-}
-
 fn chunkify(input: &str) -> Vec<DataChunk> {
     let lines_len = input.lines().count();
     input
@@ -128,9 +247,7 @@ fn chunkify(input: &str) -> Vec<DataChunk> {
             (Vec::new(), DataChunk::new()),
             |(mut coll, mut chunk), (i, line)| {
                 if line.chars().next().unwrap_or_default().is_ascii_digit() {
-                    let (dest, src, len) = line
-                        .split_whitespace()
-                        .map(parse_to_i64)
+                    let (dest, src, len) = parse::ints::<i64>(line)
                         .collect_tuple()
                         .expect("There should be 3 numbers on a line!");
                     let src_range = src..src + len;
@@ -147,27 +264,12 @@ fn chunkify(input: &str) -> Vec<DataChunk> {
         .0
 }
 
-fn proccess_seeds(seed_map: Vec<i64>, chunks: &Vec<DataChunk>) -> BTreeSet<i64> {
-    seed_map
-        .iter()
-        .map(|seed| {
-            info!("changing seed");
-            let mut num_op = *seed;
-            for chunk in chunks {
-                let valid_map = chunk
-                    .iter()
-                    .find(|(src_range, _diff)| src_range.contains(&num_op));
-                if let Some((_, diff)) = valid_map {
-                    num_op += diff;
-                }
-            }
-            num_op
-        })
-        .collect::<BTreeSet<_>>()
-}
-
 #[cfg(test)]
 mod tests {
+    use advent_of_code::generate;
+    use advent_of_code::prop::{self, Rng};
+    use advent_of_code::prop_assert_eq;
+
     use super::*;
 
     #[test]
@@ -179,59 +281,81 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        // let result_improved =
-        //     part_two_improved(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(46));
-        // assert_eq!(result, result_improved);
+        assert_eq!(
+            part_two_bruteforce(&advent_of_code::template::read_file("examples", DAY)),
+            result
+        );
     }
-}
 
-// NOTE: This is a demonstration of Bruteforce method used as the original solution.
-//
-// fn find_seed(chunks: &Vec<DataChunk>, seed_map: &[Range<i64>]) -> Option<u32> {
-//     let n = (0u64..u64::MAX).progress_count(u64::MAX).find(|n| {
-//         let mut num_op = *n;
-//         let mut found = false;
-//         for (idx, chunk) in chunks.iter().rev().enumerate() {
-//             if let Some((src_range, dest_range)) = chunk
-//                 .iter()
-//                 .find(|(_, dest_range)| dest_range.contains(&num_op))
-//             {
-//                 num_op = num_op - dest_range.start + src_range.start;
-//             }
-//             let seed_map_check = seed_map
-//                 .iter()
-//                 .any(|seed_range| seed_range.contains(&num_op));
-//             // if idx == chunks.len() - 1 && seed_map_check {
-//             //     return Some(n as u32);
-//             // }
-//             if idx == chunks.len() - 1 && seed_map_check {
-//                 found = true;
-//                 info!("FOUND a match: {n}");
-//             }
-//         }
-//         found
-//     });
-//     n.map(|n| n as u32)
-// }
-//
-// In part two we have enormous ranges of data in SEEDS so instead of processing each SEED it makes
-// sense to BRUTEFORCE in reverse and look for matches (even more so because of the huge ranges,
-// there is a big chance we will hit a match early).
-//
-// pub fn part_two_bruteforce(input: &str) -> Option<u32> {
-//     let seed_map = input
-//         .lines()
-//         .take(1)
-//         .flat_map(|line| {
-//             let (_, nums) = line.split_once(": ").expect("Should be splitable by ': '");
-//             nums.split_whitespace()
-//                 .map(parse_to_i64)
-//                 .tuples::<(_, _)>()
-//                 .map(|(seed_num, len)| seed_num..seed_num + len)
-//         })
-//         .collect_vec();
-//
-//     let chunks = chunkify(input);
-//     find_seed(&chunks, &seed_map)
-// }
+    /// Pushes a seed through every layer in sequence.
+    fn layered(chunks: &[DataChunk], seed: i64) -> i64 {
+        chunks.iter().fold(seed, |n, chunk| {
+            let diff = chunk.iter().find(|(src, _)| src.contains(&n));
+            n + diff.map_or(0, |(_, diff)| *diff)
+        })
+    }
+
+    #[test]
+    fn test_composed_map() {
+        let chunks = chunkify(&advent_of_code::template::read_file("examples", DAY));
+        let map = PiecewiseMap::compose(&chunks);
+        let inverse = map.inverse();
+        for seed in -10..200 {
+            let location = map.apply(seed);
+            assert_eq!(location, layered(&chunks, seed), "seed {seed}");
+            assert_eq!(inverse.apply(location), seed, "location {location}");
+            assert_eq!(map.min_over(seed..seed + 1), Some(location));
+        }
+        assert_eq!(inverse.inverse(), map);
+        assert_eq!(map.min_over(5..5), None);
+    }
+
+    fn random_input(rng: &mut Rng, size: usize) -> String {
+        let n = rng.range(2..=2 * size as i64 + 10);
+        let seed_ranges = rng.range(1..=4);
+        generate::day05::input(rng, n, size / 10 + 3, seed_ranges, 20)
+    }
+
+    /// Parses the input and looks each seed up layer by layer.
+    fn naive_lowest(input: &str, seed_ranges: bool) -> Option<i64> {
+        let mut blocks = input.split("\n\n");
+        let seeds = parse::ints::<i64>(blocks.next().unwrap()).collect_vec();
+        let layers = blocks
+            .map(|block| {
+                block
+                    .lines()
+                    .skip(1)
+                    .map(|line| parse::ints::<i64>(line).collect_vec())
+                    .collect_vec()
+            })
+            .collect_vec();
+        let location = |seed: i64| {
+            layers.iter().fold(seed, |n, layer| {
+                match layer.iter().find(|m| (m[1]..m[1] + m[2]).contains(&n)) {
+                    Some(m) => n - m[1] + m[0],
+                    None => n,
+                }
+            })
+        };
+        if seed_ranges {
+            seeds
+                .chunks(2)
+                .flat_map(|pair| pair[0]..pair[0] + pair[1])
+                .map(location)
+                .min()
+        } else {
+            seeds.into_iter().map(location).min()
+        }
+    }
+
+    #[test]
+    fn test_against_naive() {
+        prop::check(prop::from_fn(random_input), |input| {
+            let lowest = naive_lowest(input, false);
+            prop_assert_eq!(part_one(input), lowest.map(|n| n as u32));
+            prop_assert_eq!(part_two(input), naive_lowest(input, true));
+            Ok(())
+        });
+    }
+}
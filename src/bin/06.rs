@@ -1,74 +1,47 @@
+use advent_of_code::{math, parse};
 use itertools::Itertools;
 
 advent_of_code::solution!(6);
 
-fn parse_to_u64(input: &str) -> u64 {
-    input.parse().expect("number should be parsable")
-}
-
 pub fn part_one(input: &str) -> Option<u64> {
     let (times, records) = input.split_once('\n').expect("not valid input");
-    let op = times
-        .trim_start_matches("Time:")
-        .split_whitespace()
-        .zip(records.trim_start_matches("Distance:").split_whitespace())
-        .map(|(time, record)| (parse_to_u64(time), parse_to_u64(record)))
+    let op = parse::ints::<u64>(times)
+        .zip(parse::ints::<u64>(records))
         .collect_vec();
 
-    let mut res = 1;
-    op.iter().for_each(|(time, record)| {
-        let mut acc = 0;
-        for time_held in 1..*time {
-            if time_held * (*time - time_held) > *record {
-                acc += 1;
-            }
-        }
-        res *= acc;
-    });
+    let res = op
+        .iter()
+        .map(|&(time, record)| ways_to_win(time, record))
+        .product();
 
     Some(res)
 }
 
+fn ways_to_win(time: u64, record: u64) -> u64 {
+    math::product_above(time, record).map_or(0, |r| r.end() - r.start() + 1)
+}
+
 pub fn part_two(input: &str) -> Option<u64> {
     let (times, records) = input.split_once('\n').expect("not valid input");
-    let time = parse_to_u64(
-        &times
-            .trim_start_matches("Time:")
-            .split_whitespace()
-            .flat_map(|t| t.chars())
-            .collect::<String>(),
-    );
-    let record = 1 + parse_to_u64(
-        &records
-            .trim_start_matches("Distance:")
-            .split_whitespace()
-            .flat_map(|t| t.chars())
-            .collect::<String>(),
-    );
-
-    // NOTE: naive solution
-    //
-    // let mut acc = 0;
-    // for time_held in 0..time {
-    //     let time_left = time - time_held;
-    //     if time_held * time_left < record {
-    //         acc += 1;
-    //     } else {
-    //         break;
-    //     }
-    // }
-    // Some(time + 1 - 2 * acc)
-
-    // Quadratic formula solution
-    //      https://en.wikipedia.org/wiki/Quadratic_formula
-    let x1 = ((time as f64 + ((time.pow(2) - 4 * record) as f64).sqrt()) / 2.0).floor() as u64;
-    let x2 = ((time as f64 - ((time.pow(2) - 4 * record) as f64).sqrt()) / 2.0).ceil() as u64;
-
-    Some(x1 - x2 + 1)
+    // part two ignores the spaces between digits.
+    let joined = |line: &str| -> u64 {
+        let digits = line
+            .chars()
+            .filter(char::is_ascii_digit)
+            .collect::<String>();
+        parse::parse_int(&digits).expect("number should be parsable")
+    };
+    let (time, record) = (joined(times), joined(records));
+
+    Some(ways_to_win(time, record))
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::generate;
+    use advent_of_code::prop::{self, Rng};
+    use advent_of_code::prop_assert_eq;
+
     use super::*;
 
     #[test]
@@ -82,4 +55,50 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(71503));
     }
+
+    fn brute_force(time: u64, record: u64) -> u64 {
+        (0..=time)
+            .filter(|time_held| time_held * (time - time_held) > record)
+            .count() as u64
+    }
+
+    #[test]
+    fn test_ways_to_win_oracle() {
+        for time in 0..100 {
+            for record in (0..time * time / 4 + 2).step_by(7) {
+                assert_eq!(ways_to_win(time, record), brute_force(time, record));
+            }
+        }
+        assert_eq!(ways_to_win(71530, 940200), brute_force(71530, 940200));
+    }
+
+    /// Joined, the times stay small enough to brute force part two.
+    fn random_input(rng: &mut Rng, size: usize) -> String {
+        let races = rng.range(1..=3);
+        generate::day06::input(rng, races, size as u64 / 3 + 10)
+    }
+
+    #[test]
+    fn test_against_naive() {
+        prop::check(prop::from_fn(random_input), |input| {
+            let (times, records) = input
+                .lines()
+                .map(|line| line.split_whitespace().skip(1).collect_vec())
+                .collect_tuple()
+                .unwrap();
+            let product = times
+                .iter()
+                .zip(&records)
+                .map(|(t, d)| brute_force(t.parse().unwrap(), d.parse().unwrap()))
+                .product();
+            let joined = |values: &[&str]| values.concat().parse().unwrap();
+
+            prop_assert_eq!(part_one(input), Some(product));
+            prop_assert_eq!(
+                part_two(input),
+                Some(brute_force(joined(&times), joined(&records)))
+            );
+            Ok(())
+        });
+    }
 }
@@ -1,151 +1,41 @@
+use advent_of_code::hands::HandRules;
+use advent_of_code::parse;
 use itertools::Itertools;
-use rustc_hash::FxHashMap;
 
 advent_of_code::solution!(7);
 
-type Hands = Vec<Hand>;
+const RULES_ONE: HandRules = HandRules::new("23456789TJQKA");
+const RULES_TWO: HandRules = HandRules::new("J23456789TQKA").with_wildcards("J");
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
-struct Hand {
-    rank: u8,
-    strength: u32,
-    bid: u32,
-}
-impl Hand {
-    fn new(rank: u8, strength: u32, bid: u32) -> Self {
-        Hand {
-            rank,
-            strength,
-            bid,
-        }
-    }
-}
-
-fn parse_to_u32(n: &str) -> u32 {
-    n.parse().expect("a valid usize number")
-}
-
-fn card_val(card: &char) -> u32 {
-    match card {
-        c if c.is_ascii_digit() => c.to_digit(10).unwrap() - 1,
-        &'T' => 9,
-        &'J' => 10,
-        &'Q' => 11,
-        &'K' => 12,
-        &'A' => 13,
-        _ => panic!("should never happen!"),
-    }
-}
-
-fn get_rank_strength(cards: &str) -> (u8, u32) {
-    let mut acc = FxHashMap::default();
-    let mut strength = 0;
-    for (i, card) in cards.chars().rev().enumerate() {
-        acc.entry(card).and_modify(|val| *val += 1).or_insert(1);
-        strength += card_val(&card) * (5u32.pow((i as u32 + 1) * 2));
-    }
-
-    let rank = match acc.len() {
-        1 => 6,
-        4 => 1,
-        5 => 0,
-        2 if acc.values().contains(&4) => 5,
-        2 => 4,
-        3 if acc.values().contains(&3) => 3,
-        3 => 2,
-        _ => panic!("should never happen!"),
-    };
-
-    (rank, strength)
-}
-
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut hands = input
+fn total_winnings(input: &str, rules: &HandRules) -> u32 {
+    input
         .lines()
         .map(|line| {
-            let split = line.split_once(' ').expect("a valid parse");
-            let (cards, bid) = (split.0, parse_to_u32(split.1));
-            let (rank, strength) = get_rank_strength(cards);
-            (Hand::new(rank, strength, bid), cards)
+            let (cards, bid) = line.split_once(' ').expect("a valid parse");
+            let key = rules.key(cards).expect("a valid hand");
+            let bid: u32 = parse::parse_int(bid).expect("a valid bid");
+            (key, bid)
         })
-        .collect_vec();
-    hands.sort_unstable();
-
-    let res = hands
-        .into_iter()
+        .sorted_unstable()
         .enumerate()
-        .map(|(i, (hand, _))| hand.bid * (i as u32 + 1))
-        .sum();
-
-    Some(res)
-}
-
-fn card_val_two(card: char) -> u32 {
-    match card {
-        c if c.is_ascii_digit() => c.to_digit(10).unwrap(),
-        'J' => 1,
-        'T' => 10,
-        'Q' => 11,
-        'K' => 12,
-        'A' => 13,
-        _ => panic!("should never happen!"),
-    }
+        .map(|(i, (_, bid))| bid * (i as u32 + 1))
+        .sum()
 }
 
-fn get_rank_strength_two(cards: &str) -> (u8, u32) {
-    let mut acc = FxHashMap::default();
-    let mut strength = 0;
-    let mut j = 0;
-    for (i, card) in cards.chars().rev().enumerate() {
-        if card == 'J' {
-            j += 1;
-        } else {
-            acc.entry(card).and_modify(|val| *val += 1).or_insert(1u32);
-        }
-        strength += card_val_two(card) * (5u32.pow((i as u32 + 1) * 2));
-    }
-
-    if let Some(highest) = acc.values_mut().max() {
-        *highest += j;
-    }
-
-    let rank = match acc.len() {
-        1 | 0 => 6,
-        4 => 1,
-        5 => 0,
-        2 if acc.values().contains(&4) => 5,
-        2 => 4,
-        3 if acc.values().contains(&3) => 3,
-        3 => 2,
-        _ => panic!("should never happen!"),
-    };
-
-    (rank, strength)
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(total_winnings(input, &RULES_ONE))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut hands: Hands = input
-        .lines()
-        .map(|line| {
-            let split = line.split_once(' ').expect("a valid parse");
-            let (cards, bid) = (split.0, parse_to_u32(split.1));
-            let (rank, strength) = get_rank_strength_two(cards);
-            Hand::new(rank, strength, bid)
-        })
-        .collect_vec();
-    hands.sort_unstable();
-
-    let res = hands
-        .into_iter()
-        .enumerate()
-        .map(|(i, hand)| hand.bid * (i as u32 + 1))
-        .sum();
-
-    Some(res)
+    Some(total_winnings(input, &RULES_TWO))
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::generate;
+    use advent_of_code::prop::{self, Rng};
+    use advent_of_code::prop_assert_eq;
+
     use super::*;
 
     #[test]
@@ -159,4 +49,41 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(5905));
     }
+
+    fn random_input(rng: &mut Rng, size: usize) -> String {
+        let hands = rng.range(1..=size + 1);
+        generate::day07::input(rng, hands)
+    }
+
+    /// Ranks the hands by their group sizes, then card by card. The hand types themselves
+    /// are checked exhaustively in `hands`, this only checks the ranking and the winnings.
+    fn naive_winnings(input: &str, rules: &HandRules, order: &str) -> u32 {
+        let mut hands = input
+            .lines()
+            .map(|line| {
+                let (cards, bid) = line.split_once(' ').unwrap();
+                let groups = rules.groups(cards).unwrap();
+                let values = cards.chars().map(|c| order.find(c).unwrap()).collect_vec();
+                ((groups, values), bid.parse::<u32>().unwrap())
+            })
+            .collect_vec();
+        hands.sort();
+        (1..).zip(hands).map(|(rank, (_, bid))| rank * bid).sum()
+    }
+
+    #[test]
+    fn test_against_naive() {
+        prop::check(prop::from_fn(random_input), |input| {
+            let (one, two) = ("23456789TJQKA", "J23456789TQKA");
+            prop_assert_eq!(
+                part_one(input),
+                Some(naive_winnings(input, &RULES_ONE, one))
+            );
+            prop_assert_eq!(
+                part_two(input),
+                Some(naive_winnings(input, &RULES_TWO, two))
+            );
+            Ok(())
+        });
+    }
 }
@@ -17,7 +17,7 @@ impl<'a> Directions<'a> {
 
 type DirTable<'a> = FxHashMap<&'a str, Directions<'a>>;
 
-fn process_line(line: &str) -> (&str, Directions) {
+fn process_line(line: &str) -> (&str, Directions<'_>) {
     let (id, left_right) = line.split_once(" = ").expect("valid parse");
     let (left, right) = left_right
         .trim_matches(|c| c == '(' || c == ')')
@@ -92,22 +92,11 @@ fn process_line_two(line: &str) -> (u32, DirectionsTwo) {
     )
 }
 
-fn gcd(a: usize, b: usize) -> usize {
-    if a == 0 {
-        return b;
-    } else if b == 0 {
-        return a;
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b > 0 {
+        (a, b) = (b, a % b);
     }
-
-    let mut r = a % b;
-    let mut a;
-    let mut b = b / r * r + b % r;
-    while r > 0 {
-        a = b;
-        b = r;
-        r = a % b;
-    }
-    b
+    a
 }
 
 fn lcm(a: usize, b: usize) -> usize {
@@ -174,6 +163,10 @@ pub fn part_two(input: &str) -> Option<usize> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::generate;
+    use advent_of_code::prop::{self, Rng};
+    use advent_of_code::prop_assert_eq;
+
     use super::*;
 
     // #[test]
@@ -187,4 +180,49 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(6));
     }
+
+    fn random_input(rng: &mut Rng, size: usize) -> String {
+        let instructions = rng.range(1..=5);
+        let cycles = (0..rng.range(1..=4))
+            .map(|_| rng.range(1..=size / 25 + 2))
+            .collect_vec();
+        generate::day08::input(rng, instructions, &cycles)
+    }
+
+    /// Moves all the given walkers one step at a time until they're all done.
+    fn naive_steps(input: &str, starts: &str, done: &dyn Fn(&str) -> bool) -> usize {
+        let (instructions, network) = input.split_once("\n\n").unwrap();
+        let network = network
+            .lines()
+            .map(|line| (&line[..3], (&line[7..10], &line[12..15])))
+            .collect::<FxHashMap<_, _>>();
+        let mut walkers = network
+            .keys()
+            .filter(|n| n.ends_with(starts))
+            .copied()
+            .collect_vec();
+        let mut steps = 0;
+        for turn in instructions.chars().cycle() {
+            if walkers.iter().all(|n| done(n)) {
+                break;
+            }
+            for node in &mut walkers {
+                let (left, right) = network[node];
+                *node = if turn == 'L' { left } else { right };
+            }
+            steps += 1;
+        }
+        steps
+    }
+
+    #[test]
+    fn test_against_naive() {
+        prop::check(prop::from_fn(random_input), |input| {
+            let one = naive_steps(input, "AAA", &|node| node == "ZZZ");
+            prop_assert_eq!(part_one(input), Some(one as u32));
+            let two = naive_steps(input, "A", &|node| node.ends_with('Z'));
+            prop_assert_eq!(part_two(input), Some(two));
+            Ok(())
+        });
+    }
 }
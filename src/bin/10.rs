@@ -4,6 +4,8 @@ use std::{
     rc::Rc,
 };
 
+use advent_of_code::viz::{self, Image, Rgb, Visualize};
+use advent_of_code::{bitset::BitGrid, polygon};
 use itertools::Itertools;
 use range_ext::intersect::{Intersect, IntersectionExt};
 
@@ -93,48 +95,55 @@ fn find_pipe(map: &[Vec<char>], s_pos: Coord) -> Option<Coord> {
         let (Ok(x), Ok(y)) = (x, y) else {
             continue;
         };
-        if valid_inputs.contains(map[y][x]) {
+        // 'S' may be on the edge of the map.
+        let Some(&ch) = map.get(y).and_then(|line| line.get(x)) else {
+            continue;
+        };
+        if valid_inputs.contains(ch) {
             return Some(new_pos);
         }
     }
     None
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let map = input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec();
-
-    let s_pos = Coord::find_s_from_input(input.find('S')?, map[0].len());
-
+fn walk_loop(map: &[Vec<char>], s_pos: Coord) -> Option<Vec<Coord>> {
     let mut last_pos = s_pos;
-    let mut curr_pos = find_pipe(&map, s_pos)?;
+    let mut curr_pos = find_pipe(map, s_pos)?;
     let mut ch = map[curr_pos.y as usize][curr_pos.x as usize];
 
-    let mut r = Vec::with_capacity(16384);
-    r.push('S');
+    let mut tiles = Vec::with_capacity(16384);
+    tiles.push(s_pos);
     while ch != 'S' {
-        //
-        r.push(ch);
+        tiles.push(curr_pos);
         let movement = curr_pos - last_pos;
         last_pos = curr_pos;
         let next_move = char_to_move(&ch, movement)?;
         curr_pos = last_pos + next_move;
         ch = map[curr_pos.y as usize][curr_pos.x as usize];
     }
-    Some(r.len() / 2)
+    Some(tiles)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let map = input
+        .lines()
+        .map(|line| line.chars().collect_vec())
+        .collect_vec();
+
+    let s_pos = Coord::find_s_from_input(input.find('S')?, map[0].len());
+
+    Some(walk_loop(&map, s_pos)?.len() / 2)
 }
 
 fn collect_ranges_and_validate_positions(
-    valid_positions: &mut [Vec<bool>],
+    valid_positions: &mut BitGrid,
     s_pos: Coord,
     map: &[Vec<char>],
 ) -> Vec<Vec<RangeInclusive<i32>>> {
     let mut ranges: Vec<Vec<RangeInclusive<i32>>> =
         map.iter().map(|_| Vec::with_capacity(256)).collect_vec();
 
-    valid_positions[s_pos.y as usize][s_pos.x as usize] = false;
+    valid_positions.remove(s_pos.x as usize, s_pos.y as usize);
 
     let mut last_pos = s_pos;
     let mut curr_pos = find_pipe(map, s_pos).expect("to find a connected pipe");
@@ -145,7 +154,7 @@ fn collect_ranges_and_validate_positions(
 
     while ch != 'S' {
         ch = map[curr_pos.y as usize][curr_pos.x as usize];
-        valid_positions[curr_pos.y as usize][curr_pos.x as usize] = false;
+        valid_positions.remove(curr_pos.x as usize, curr_pos.y as usize);
         let movement = curr_pos - last_pos;
         match movement {
             Coord { x: 1, .. } => {
@@ -274,10 +283,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     let s_pos = Coord::find_s_from_input(input.find('S')?, map[0].len());
 
-    let mut valid_positions = map
-        .iter()
-        .map(|line| line.iter().map(|_| true).collect_vec())
-        .collect_vec();
+    let mut valid_positions = BitGrid::full(map[0].len(), map.len());
 
     let mut ranges = collect_ranges_and_validate_positions(&mut valid_positions, s_pos, &map);
     ranges.iter_mut().for_each(combine_ranges);
@@ -298,6 +304,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         .unwrap_or(0);
 
     let mut sum = 0;
+    let mut counted = BitGrid::new(map[0].len(), map.len());
     for (range_line_id, range_line) in ranges.iter().enumerate().skip(first_pos + 1) {
         let mod_range_line = range_line
             .iter()
@@ -361,17 +368,245 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
         for range in upper_ranges.borrow().iter() {
             for i in range.clone() {
-                if valid_positions[range_line_id][i as usize] {
+                if valid_positions.contains(i as usize, range_line_id) {
+                    counted.insert(i as usize, range_line_id);
                     sum += 1;
                 }
             }
         }
     }
+
+    if cfg!(debug_assertions) {
+        let loop_tiles = walk_loop(&map, s_pos).expect("the loop to close");
+        assert_eq!(
+            sum,
+            enclosed_by_pick(&loop_tiles),
+            "range merging disagrees with Pick's theorem"
+        );
+        assert_eq!(
+            sum,
+            enclosed_by_scanline(&map, &loop_tiles),
+            "range merging disagrees with the scanline count"
+        );
+    }
+
+    if viz::enabled() {
+        visualize(&map, s_pos, &counted);
+    }
     Some(sum)
 }
 
+// Cross-checks for part two: Pick's theorem over the loop walk and an even-odd scanline fill.
+
+fn enclosed_by_pick(loop_tiles: &[Coord]) -> u32 {
+    let vertices = loop_tiles
+        .iter()
+        .map(|c| (c.x as i64, c.y as i64))
+        .collect_vec();
+    polygon::interior_points(&vertices) as u32
+}
+
+fn enclosed_by_scanline(map: &[Vec<char>], loop_tiles: &[Coord]) -> u32 {
+    let mut on_loop = map.iter().map(|l| vec![false; l.len()]).collect_vec();
+    for tile in loop_tiles {
+        on_loop[tile.y as usize][tile.x as usize] = true;
+    }
+    let s_char = s_pipe(loop_tiles);
+
+    let count = polygon::scanline_count(
+        map[0].len(),
+        map.len(),
+        |x, y| on_loop[y][x],
+        |x, y| match map[y][x] {
+            'S' => "|LJ".contains(s_char),
+            ch => "|LJ".contains(ch),
+        },
+    );
+    count as u32
+}
+
+// Visualisation of part two (`--viz`, `--viz-out <path>`): the loop is drawn with box-drawing
+// characters, the tiles counted by the range merging are green and tiles where it disagrees
+// with the scanline fill are red.
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Tile {
+    Loop(char),
+    Inside,
+    Outside,
+    Unused(char),
+    Mismatch,
+}
+
+/// Which sides (north, east, south, west) a pipe connects to.
+fn pipe_arms(ch: char) -> [bool; 4] {
+    match ch {
+        '|' => [true, false, true, false],
+        '-' => [false, true, false, true],
+        'L' => [true, true, false, false],
+        'J' => [true, false, false, true],
+        '7' => [false, false, true, true],
+        'F' => [false, true, true, false],
+        _ => [false; 4],
+    }
+}
+
+fn box_char(ch: char) -> char {
+    match ch {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        _ => '·',
+    }
+}
+
+/// The pipe hidden under 'S', derived from the two loop tiles next to it.
+fn s_pipe(loop_tiles: &[Coord]) -> char {
+    let (s, next, prev) = (
+        loop_tiles[0],
+        loop_tiles[1],
+        loop_tiles[loop_tiles.len() - 1],
+    );
+    let side = |c: Coord| match (c - s).x {
+        1 => 1,
+        -1 => 3,
+        _ if c.y < s.y => 0,
+        _ => 2,
+    };
+    let mut arms = [false; 4];
+    arms[side(next)] = true;
+    arms[side(prev)] = true;
+    "|-LJ7F"
+        .chars()
+        .find(|&ch| pipe_arms(ch) == arms)
+        .unwrap_or('S')
+}
+
+fn classify_tiles(map: &[Vec<char>], s_pos: Coord, counted: &BitGrid) -> Vec<Vec<Tile>> {
+    let loop_tiles = walk_loop(map, s_pos).unwrap_or_default();
+    let mut on_loop = BitGrid::new(map[0].len(), map.len());
+    for tile in &loop_tiles {
+        on_loop.insert(tile.x as usize, tile.y as usize);
+    }
+    let s_char = s_pipe(&loop_tiles);
+    let inside = polygon::scanline_inside(
+        map[0].len(),
+        map.len(),
+        |x, y| on_loop.contains(x, y),
+        |x, y| match map[y][x] {
+            'S' => "|LJ".contains(s_char),
+            ch => "|LJ".contains(ch),
+        },
+    );
+
+    map.iter()
+        .enumerate()
+        .map(|(y, line)| {
+            line.iter()
+                .enumerate()
+                .map(|(x, &ch)| {
+                    let ch = if ch == 'S' { s_char } else { ch };
+                    match (on_loop.contains(x, y), counted.contains(x, y), inside[y][x]) {
+                        (true, false, _) => Tile::Loop(ch),
+                        (false, true, true) => Tile::Inside,
+                        (false, false, false) if ch == '.' => Tile::Outside,
+                        (false, false, false) => Tile::Unused(ch),
+                        _ => Tile::Mismatch,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn render_terminal(tiles: &[Vec<Tile>]) -> String {
+    tiles
+        .iter()
+        .map(|line| {
+            line.iter()
+                .map(|tile| match tile {
+                    Tile::Loop(ch) => format!("\x1b[1;33m{}\x1b[0m", box_char(*ch)),
+                    Tile::Inside => "\x1b[32m█\x1b[0m".to_string(),
+                    Tile::Outside => "\x1b[2m·\x1b[0m".to_string(),
+                    Tile::Unused(ch) => format!("\x1b[2;34m{}\x1b[0m", box_char(*ch)),
+                    Tile::Mismatch => "\x1b[41m?\x1b[0m".to_string(),
+                })
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+const TILE_PX: usize = 3;
+const COLOR_BACKGROUND: Rgb = [20, 20, 30];
+const COLOR_LOOP: Rgb = [250, 200, 40];
+const COLOR_INSIDE: Rgb = [40, 170, 70];
+const COLOR_UNUSED: Rgb = [70, 80, 120];
+const COLOR_MISMATCH: Rgb = [220, 40, 40];
+
+/// Draws every tile as a 3x3 block, pipes as a centre pixel with arms towards their neighbours.
+fn render_image(tiles: &[Vec<Tile>]) -> Image {
+    let mut image = Image::new(
+        tiles[0].len() * TILE_PX,
+        tiles.len() * TILE_PX,
+        COLOR_BACKGROUND,
+    );
+    for (y, line) in tiles.iter().enumerate() {
+        for (x, tile) in line.iter().enumerate() {
+            let (px, py) = (x * TILE_PX, y * TILE_PX);
+            let (pipe, color) = match *tile {
+                Tile::Loop(ch) => (ch, COLOR_LOOP),
+                Tile::Unused(ch) => (ch, COLOR_UNUSED),
+                Tile::Inside => {
+                    image.fill_rect(px, py, TILE_PX, TILE_PX, COLOR_INSIDE);
+                    continue;
+                }
+                Tile::Mismatch => {
+                    image.fill_rect(px, py, TILE_PX, TILE_PX, COLOR_MISMATCH);
+                    continue;
+                }
+                Tile::Outside => continue,
+            };
+            let [north, east, south, west] = pipe_arms(pipe);
+            image.set(px + 1, py + 1, color);
+            for (arm, (ax, ay)) in
+                [north, east, south, west]
+                    .into_iter()
+                    .zip([(1, 0), (2, 1), (1, 2), (0, 1)])
+            {
+                if arm {
+                    image.set(px + ax, py + ay, color);
+                }
+            }
+        }
+    }
+    image
+}
+
+struct LoopView(Vec<Vec<Tile>>);
+
+impl Visualize for LoopView {
+    fn to_text(&self) -> String {
+        render_terminal(&self.0)
+    }
+
+    fn to_image(&self) -> Image {
+        render_image(&self.0)
+    }
+}
+
+fn visualize(map: &[Vec<char>], s_pos: Coord, counted: &BitGrid) {
+    viz::frame(&LoopView(classify_tiles(map, s_pos, counted)));
+}
+
 #[cfg(test)]
 mod tests {
+    use advent_of_code::generate::{self, day10::PipeMaze};
+    use advent_of_code::prop::{self, Rng};
+    use advent_of_code::prop_assert_eq;
+
     use super::*;
     #[test]
     fn test_coord_add() {
@@ -399,4 +634,87 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(10));
     }
+
+    #[test]
+    fn test_part_two_cross_check() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let map = input
+            .lines()
+            .map(|line| line.chars().collect_vec())
+            .collect_vec();
+        let s_pos = Coord::find_s_from_input(input.find('S').unwrap(), map[0].len());
+        let loop_tiles = walk_loop(&map, s_pos).unwrap();
+
+        assert_eq!(enclosed_by_pick(&loop_tiles), 10);
+        assert_eq!(enclosed_by_scanline(&map, &loop_tiles), 10);
+    }
+
+    #[test]
+    fn test_stray_pipe_above_s() {
+        // the '7' above 'S' points down into it, but isn't part of the loop.
+        let input = ".7...\n.S-7.\n.|.|.\n.L-J.\n.....";
+        let map = input
+            .lines()
+            .map(|line| line.chars().collect_vec())
+            .collect_vec();
+        let s_pos = Coord::find_s_from_input(input.find('S').unwrap(), map[0].len());
+        let loop_tiles = walk_loop(&map, s_pos).unwrap();
+
+        assert_eq!(enclosed_by_scanline(&map, &loop_tiles), 1);
+        assert_eq!(part_two(input), Some(1));
+    }
+
+    #[test]
+    fn test_visualisation_tiles() {
+        let input = ".....\n.S-7.\n.|.|.\n.L-J.\n.....";
+        let map = input
+            .lines()
+            .map(|line| line.chars().collect_vec())
+            .collect_vec();
+        let s_pos = Coord::find_s_from_input(input.find('S').unwrap(), map[0].len());
+        let mut counted = BitGrid::new(5, 5);
+        counted.insert(2, 2);
+
+        let tiles = classify_tiles(&map, s_pos, &counted);
+        assert_eq!(tiles[1][1], Tile::Loop('F'));
+        assert_eq!(tiles[2][2], Tile::Inside);
+        assert_eq!(tiles[0][0], Tile::Outside);
+        assert!(render_terminal(&tiles).contains('┌'));
+
+        let image = render_image(&tiles);
+        assert_eq!((image.width(), image.height()), (15, 15));
+        assert_eq!(image.get(7, 7), Some(COLOR_INSIDE));
+        // the 'F' under 'S' has arms to the east and south, not to the north.
+        assert_eq!(image.get(5, 4), Some(COLOR_LOOP));
+        assert_eq!(image.get(4, 3), Some(COLOR_BACKGROUND));
+
+        counted.clear();
+        let tiles = classify_tiles(&map, s_pos, &counted);
+        assert_eq!(tiles[2][2], Tile::Mismatch);
+    }
+
+    fn random_maze(rng: &mut Rng, size: usize) -> PipeMaze {
+        let (width, height) = (rng.range(1..=size / 5 + 2), rng.range(1..=size / 5 + 2));
+        let cells = rng.range(1..=width * height);
+        generate::day10::input(rng, width, height, cells)
+    }
+
+    #[test]
+    fn test_against_random_loops() {
+        prop::check(prop::from_fn(random_maze), |maze| {
+            let (input, inside) = (&maze.input, maze.enclosed as u32);
+            prop_assert_eq!(part_one(input), Some(maze.farthest));
+            prop_assert_eq!(part_two(input), Some(inside));
+
+            let map = input
+                .lines()
+                .map(|line| line.chars().collect_vec())
+                .collect_vec();
+            let s_pos = Coord::find_s_from_input(input.find('S').unwrap(), map[0].len());
+            let loop_tiles = walk_loop(&map, s_pos).unwrap();
+            prop_assert_eq!(enclosed_by_pick(&loop_tiles), inside);
+            prop_assert_eq!(enclosed_by_scanline(&map, &loop_tiles), inside);
+            Ok(())
+        });
+    }
 }
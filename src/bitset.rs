@@ -0,0 +1,429 @@
+//! Fixed-size sets of small integers and grid positions that store one bit per element,
+//! e.g. for tracking visited tiles.
+use std::fmt::Debug;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A set of integers in `0..len`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    /// Creates an empty set that can hold the integers `0..len`.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    /// Creates a set containing all integers `0..len`.
+    pub fn full(len: usize) -> Self {
+        let mut set = Self {
+            words: vec![u64::MAX; len.div_ceil(WORD_BITS)],
+            len,
+        };
+        set.clear_padding();
+        set
+    }
+
+    /// The number of integers the set can hold, not the number of integers in it (see [`BitSet::count`]).
+    pub fn capacity(&self) -> usize {
+        self.len
+    }
+
+    /// Adds `i`, returning whether it was newly inserted.
+    ///
+    /// # Panics
+    /// If `i` is out of bounds.
+    #[inline]
+    pub fn insert(&mut self, i: usize) -> bool {
+        let (word, mask) = self.locate(i);
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] |= mask;
+        !was_set
+    }
+
+    /// Removes `i`, returning whether it was present.
+    ///
+    /// # Panics
+    /// If `i` is out of bounds.
+    #[inline]
+    pub fn remove(&mut self, i: usize) -> bool {
+        let (word, mask) = self.locate(i);
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        was_set
+    }
+
+    /// Returns whether `i` is in the set. Out of bounds integers are never in the set.
+    #[inline]
+    pub fn contains(&self, i: usize) -> bool {
+        // bits past `len` in the last word are always zero.
+        self.words
+            .get(i / WORD_BITS)
+            .is_some_and(|w| w & (1 << (i % WORD_BITS)) != 0)
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Yields the integers in the set in ascending order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            words: &self.words,
+            index: 0,
+            current: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    /// Adds all integers of `other` to `self`.
+    ///
+    /// # Panics
+    /// If the sets have different capacities.
+    pub fn union_with(&mut self, other: &BitSet) {
+        self.zip_words(other, |a, b| a | b);
+    }
+
+    /// Removes all integers from `self` that are not in `other`.
+    ///
+    /// # Panics
+    /// If the sets have different capacities.
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        self.zip_words(other, |a, b| a & b);
+    }
+
+    /// Removes all integers of `other` from `self`.
+    ///
+    /// # Panics
+    /// If the sets have different capacities.
+    pub fn difference_with(&mut self, other: &BitSet) {
+        self.zip_words(other, |a, b| a & !b);
+    }
+
+    /// Returns the set of integers in `self` or `other`.
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        let mut set = self.clone();
+        set.union_with(other);
+        set
+    }
+
+    /// Returns the set of integers in both `self` and `other`.
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        let mut set = self.clone();
+        set.intersect_with(other);
+        set
+    }
+
+    #[inline]
+    fn locate(&self, i: usize) -> (usize, u64) {
+        assert!(
+            i < self.len,
+            "index {i} out of bounds for bitset of size {}",
+            self.len
+        );
+        (i / WORD_BITS, 1 << (i % WORD_BITS))
+    }
+
+    fn zip_words(&mut self, other: &BitSet, op: impl Fn(u64, u64) -> u64) {
+        assert_eq!(self.len, other.len, "bitsets have different sizes");
+        for (a, &b) in self.words.iter_mut().zip(&other.words) {
+            *a = op(*a, b);
+        }
+    }
+
+    fn clear_padding(&mut self) {
+        let used = self.len % WORD_BITS;
+        if let (Some(last), true) = (self.words.last_mut(), used > 0) {
+            *last &= (1 << used) - 1;
+        }
+    }
+}
+
+impl Debug for BitSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<T: IntoIterator<Item = usize>>(&mut self, iter: T) {
+        for i in iter {
+            self.insert(i);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a BitSet {
+    type Item = usize;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator returned by [`BitSet::iter`].
+pub struct Iter<'a> {
+    words: &'a [u64],
+    index: usize,
+    current: u64,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.index += 1;
+            self.current = *self.words.get(self.index)?;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        // clear the lowest set bit.
+        self.current &= self.current - 1;
+        Some(self.index * WORD_BITS + bit)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of `(x, y)` positions on a `width` x `height` grid, stored row by row in a [`BitSet`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    bits: BitSet,
+    width: usize,
+    height: usize,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            bits: BitSet::new(width * height),
+            width,
+            height,
+        }
+    }
+
+    pub fn full(width: usize, height: usize) -> Self {
+        Self {
+            bits: BitSet::full(width * height),
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    fn index(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) out of bounds for {}x{} grid",
+            self.width,
+            self.height
+        );
+        y * self.width + x
+    }
+
+    /// Adds `(x, y)`, returning whether it was newly inserted.
+    ///
+    /// # Panics
+    /// If the position is outside the grid.
+    #[inline]
+    pub fn insert(&mut self, x: usize, y: usize) -> bool {
+        let i = self.index(x, y);
+        self.bits.insert(i)
+    }
+
+    /// Removes `(x, y)`, returning whether it was present.
+    ///
+    /// # Panics
+    /// If the position is outside the grid.
+    #[inline]
+    pub fn remove(&mut self, x: usize, y: usize) -> bool {
+        let i = self.index(x, y);
+        self.bits.remove(i)
+    }
+
+    /// Returns whether `(x, y)` is in the set. Positions outside the grid are never in the set.
+    #[inline]
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.bits.contains(y * self.width + x)
+    }
+
+    pub fn count(&self) -> usize {
+        self.bits.count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// Yields the positions in the set row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let width = self.width;
+        self.bits.iter().map(move |i| (i % width, i / width))
+    }
+
+    /// # Panics
+    /// If the grids have different dimensions.
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        self.bits.union_with(&other.bits);
+    }
+
+    /// # Panics
+    /// If the grids have different dimensions.
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        self.bits.intersect_with(&other.bits);
+    }
+
+    /// # Panics
+    /// If the grids have different dimensions.
+    pub fn difference_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        self.bits.difference_with(&other.bits);
+    }
+
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        let mut grid = self.clone();
+        grid.union_with(other);
+        grid
+    }
+
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        let mut grid = self.clone();
+        grid.intersect_with(other);
+        grid
+    }
+
+    fn assert_same_size(&self, other: &BitGrid) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grids have different dimensions"
+        );
+    }
+}
+
+impl Debug for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            let row = (0..self.width)
+                .map(|x| if self.contains(x, y) { '#' } else { '.' })
+                .collect::<String>();
+            writeln!(f, "{row}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Extend<(usize, usize)> for BitGrid {
+    fn extend<T: IntoIterator<Item = (usize, usize)>>(&mut self, iter: T) {
+        for (x, y) in iter {
+            self.insert(x, y);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove_contains() {
+        let mut set = BitSet::new(130);
+        assert!(set.insert(0));
+        assert!(set.insert(64));
+        assert!(set.insert(129));
+        assert!(!set.insert(64));
+        assert!(set.contains(129));
+        assert!(!set.contains(130));
+        assert_eq!(set.count(), 3);
+
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 129]);
+
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds_panics() {
+        BitSet::new(10).insert(10);
+    }
+
+    #[test]
+    fn full_set_has_no_padding() {
+        let set = BitSet::full(70);
+        assert_eq!(set.count(), 70);
+        assert_eq!(set.iter().last(), Some(69));
+        assert_eq!(BitSet::full(64).count(), 64);
+        assert_eq!(BitSet::full(0).iter().next(), None);
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut a = BitSet::new(200);
+        let mut b = BitSet::new(200);
+        a.extend([1, 2, 3, 100, 199]);
+        b.extend([2, 3, 4, 199]);
+
+        assert_eq!(
+            a.union(&b).iter().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 100, 199]
+        );
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), [2, 3, 199]);
+        a.difference_with(&b);
+        assert_eq!(a.iter().collect::<Vec<_>>(), [1, 100]);
+    }
+
+    #[test]
+    fn grid_positions() {
+        let mut grid = BitGrid::new(5, 3);
+        grid.extend([(0, 0), (4, 0), (2, 1), (4, 2)]);
+        assert!(grid.contains(2, 1));
+        assert!(!grid.contains(1, 2));
+        assert!(!grid.contains(5, 0));
+        assert_eq!(grid.count(), 4);
+        assert_eq!(
+            grid.iter().collect::<Vec<_>>(),
+            [(0, 0), (4, 0), (2, 1), (4, 2)]
+        );
+        assert_eq!(format!("{grid:?}"), "#...#\n..#..\n....#\n");
+
+        let mut inverted = BitGrid::full(5, 3);
+        inverted.difference_with(&grid);
+        assert_eq!(inverted.count(), 11);
+        assert!(inverted.intersection(&grid).is_empty());
+        assert_eq!(inverted.union(&grid), BitGrid::full(5, 3));
+    }
+}
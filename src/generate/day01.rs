@@ -0,0 +1,25 @@
+//! Calibration document lines.
+use itertools::Itertools;
+
+use crate::prop::Rng;
+
+pub const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Lines glued together from up to `max_fragments` digits, digit words (which often overlap)
+/// and random letters each.
+pub fn input(rng: &mut Rng, lines: usize, max_fragments: usize) -> String {
+    let line = |rng: &mut Rng| {
+        let mut line = String::new();
+        for _ in 0..rng.range(1..=max_fragments) {
+            match rng.range(0..3) {
+                0 => line.push(char::from(b'0' + rng.range(1..=9u8))),
+                1 => line.push_str(rng.choose::<&str>(&WORDS)),
+                _ => line.push(char::from(rng.range(b'a'..=b'z'))),
+            }
+        }
+        line
+    };
+    (0..lines).map(|_| line(rng)).join("\n")
+}
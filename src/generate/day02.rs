@@ -0,0 +1,24 @@
+//! Games of drawing colored cubes from a bag.
+use itertools::Itertools;
+
+use crate::prop::Rng;
+
+pub const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+/// `games` games of up to `max_draws` draws of 1 to 20 cubes per color.
+pub fn input(rng: &mut Rng, games: usize, max_draws: usize) -> String {
+    let draw = |rng: &mut Rng| {
+        let mut colors = COLORS.to_vec();
+        rng.shuffle(&mut colors);
+        colors[..rng.range(1..=3)]
+            .iter()
+            .map(|color| format!("{} {color}", rng.range(1..=20)))
+            .join(", ")
+    };
+    (1..=games)
+        .map(|id| {
+            let draws = (0..rng.range(1..=max_draws)).map(|_| draw(rng)).join("; ");
+            format!("Game {id}: {draws}")
+        })
+        .join("\n")
+}
@@ -0,0 +1,26 @@
+//! Engine schematics.
+use itertools::Itertools;
+
+use crate::prop::Rng;
+
+pub const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '-', '&'];
+
+/// A `width` x `height` schematic of numbers (up to three digits, like the real inputs) and symbols.
+pub fn input(rng: &mut Rng, width: usize, height: usize) -> String {
+    (0..height)
+        .map(|_| {
+            let mut row = String::new();
+            let mut run = 0;
+            for _ in 0..width {
+                let c = match rng.range(0..10) {
+                    0..=3 if run < 3 => char::from(b'0' + rng.range(0..=9u8)),
+                    4 => *rng.choose(&SYMBOLS),
+                    _ => '.',
+                };
+                run = if c.is_ascii_digit() { run + 1 } else { 0 };
+                row.push(c);
+            }
+            row
+        })
+        .join("\n")
+}
@@ -0,0 +1,46 @@
+//! Scratchcards.
+use itertools::Itertools;
+
+use crate::prop::Rng;
+
+/// Won copies of a single card are kept below this, so that the total fits into a `u32`.
+const MAX_COPIES: u32 = 1 << 17;
+
+/// `cards` cards with `winning` and `own` distinct numbers below 100 on each side. Cards never
+/// win copies of cards past the end of the table.
+///
+/// # Panics
+/// If there are more than 99 numbers on a card.
+pub fn input(rng: &mut Rng, cards: usize, winning: usize, own: usize) -> String {
+    assert!(winning + own < 100, "too many numbers on a card");
+
+    // matches are picked back to front, knowing how many copies the later cards are worth.
+    let mut copies = vec![0u32; cards];
+    let mut matches = vec![0; cards];
+    for id in (0..cards).rev() {
+        let mut m = rng.range(0..=winning.min(own).min(cards - 1 - id));
+        while copies[id + 1..][..m].iter().sum::<u32>() >= MAX_COPIES {
+            m -= 1;
+        }
+        matches[id] = m;
+        copies[id] = 1 + copies[id + 1..][..m].iter().sum::<u32>();
+    }
+
+    (1..=cards)
+        .zip(matches)
+        .map(|(id, matches)| {
+            let mut numbers = (1..100).collect_vec();
+            rng.shuffle(&mut numbers);
+            let mut mine = numbers[..matches].to_vec();
+            mine.extend(&numbers[winning..][..own - matches]);
+            rng.shuffle(&mut mine);
+
+            let side = |ns: &[u32]| ns.iter().map(|n| format!("{n:>2}")).join(" ");
+            format!(
+                "Card {id:>3}: {} | {}",
+                side(&numbers[..winning]),
+                side(&mine)
+            )
+        })
+        .join("\n")
+}
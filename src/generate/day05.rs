@@ -0,0 +1,58 @@
+//! Almanacs of seed mapping layers.
+use itertools::Itertools;
+
+use crate::prop::Rng;
+
+pub const LAYERS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// `seed_ranges` pairs of seed numbers below `n` and range lengths up to `max_seed_len`.
+/// Like the real inputs, every layer permutes the segments of a partition of `0..n` (cut
+/// at up to `max_cuts` points), sometimes leaving out segments that stay in place.
+///
+/// # Panics
+/// If `n < 2`.
+pub fn input(
+    rng: &mut Rng,
+    n: i64,
+    max_cuts: usize,
+    seed_ranges: usize,
+    max_seed_len: i64,
+) -> String {
+    let seeds = (0..seed_ranges)
+        .map(|_| {
+            let start = rng.range(0..n);
+            format!("{start} {}", rng.range(1..=max_seed_len))
+        })
+        .join(" ");
+
+    let layers = LAYERS.iter().map(|name| {
+        let mut cuts = (0..rng.range(0..=max_cuts))
+            .map(|_| rng.range(1..n))
+            .collect_vec();
+        cuts.extend([0, n]);
+        cuts.sort();
+        cuts.dedup();
+        let mut segments = cuts.into_iter().tuple_windows().collect_vec();
+        rng.shuffle(&mut segments);
+
+        let mut dest = 0;
+        let mut lines = Vec::new();
+        for (start, end) in segments {
+            if dest != start || lines.is_empty() || rng.bool(0.5) {
+                lines.push(format!("{dest} {start} {}", end - start));
+            }
+            dest += end - start;
+        }
+        rng.shuffle(&mut lines);
+        format!("{name} map:\n{}", lines.join("\n"))
+    });
+    format!("seeds: {seeds}\n\n{}", layers.format("\n\n"))
+}
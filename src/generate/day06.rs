@@ -0,0 +1,28 @@
+//! Boat race records.
+use itertools::Itertools;
+
+use crate::prop::Rng;
+
+/// `races` races of 2 to `max_time` milliseconds, with records that can be beaten.
+///
+/// # Panics
+/// If `max_time < 2`.
+pub fn input(rng: &mut Rng, races: usize, max_time: u64) -> String {
+    let races = (0..races)
+        .map(|_| {
+            let time = rng.range(2..=max_time);
+            (time, rng.range(0..(time / 2) * (time - time / 2)))
+        })
+        .collect_vec();
+    let width = races
+        .iter()
+        .map(|(_, record)| record.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let row = |name: &str, values: Vec<u64>| {
+        let values = values.iter().map(|v| format!("{v:>width$}")).join("  ");
+        format!("{name:<9} {values}")
+    };
+    let (times, records) = races.into_iter().unzip();
+    format!("{}\n{}", row("Time:", times), row("Distance:", records))
+}
@@ -0,0 +1,28 @@
+//! Camel Cards hands and bids.
+use itertools::Itertools;
+
+use crate::prop::Rng;
+
+pub const CARDS: &str = "23456789TJQKA";
+
+/// `hands` distinct hands with bids up to 1000. Each hand is drawn from a few cards only,
+/// so that pairs and jokers are common.
+///
+/// # Panics
+/// If `hands` exceeds the number of distinct hands.
+pub fn input(rng: &mut Rng, hands: usize) -> String {
+    assert!(hands <= 13usize.pow(5), "not enough distinct hands");
+    let cards = CARDS.chars().collect_vec();
+    let mut drawn = std::collections::HashSet::new();
+    let mut lines = Vec::with_capacity(hands);
+    while lines.len() < hands {
+        let pool = (0..rng.range(1..=5))
+            .map(|_| *rng.choose(&cards))
+            .collect_vec();
+        let hand = (0..5).map(|_| *rng.choose(&pool)).collect::<String>();
+        if drawn.insert(hand.clone()) {
+            lines.push(format!("{hand} {}", rng.range(1..=1000)));
+        }
+    }
+    lines.join("\n")
+}
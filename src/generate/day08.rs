@@ -0,0 +1,94 @@
+//! Networks of nodes and left/right instructions.
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::prop::Rng;
+
+/// A network with the structure of the real inputs, which the solutions rely on: walking
+/// from the `i`-th `..A` node (`AAA` first) reaches its `..Z` node after exactly
+/// `instructions * cycles[i]` steps, and then loops back, since `..Z` leads where `..A` does.
+/// Turns that are never taken lead to random nodes.
+///
+/// Every step of a walk visits a new node, so the real inputs' cycle lengths would need far
+/// more nodes than there are names: see [`full`] for sizes that fit.
+///
+/// # Panics
+/// If `instructions` is 0, or `cycles` is empty or contains 0.
+pub fn input(rng: &mut Rng, instructions: usize, cycles: &[usize]) -> String {
+    assert!(instructions > 0 && !cycles.is_empty() && !cycles.contains(&0));
+    let instructions = (0..instructions)
+        .map(|_| *rng.choose(&['L', 'R']))
+        .collect::<String>();
+    let mut names = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+
+    // (node, whether it's the end of a walk, left, right); turns yet to be decided are empty.
+    let mut nodes = Vec::<(String, bool, String, String)>::new();
+    for (ghost, cycle) in cycles.iter().enumerate() {
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => {
+                let start = unused_name(rng, &mut names, Some('A'));
+                let end = format!("{}Z", &start[..2]);
+                names.insert(end.clone());
+                (start, end)
+            }
+        };
+        let len = instructions.len() * cycle;
+        let mut path = vec![start];
+        path.extend((1..len).map(|_| unused_name(rng, &mut names, None)));
+        path.push(end);
+        for (i, turn) in instructions.chars().cycle().take(len).enumerate() {
+            let next = path[i + 1].clone();
+            let (left, right) = match turn {
+                'L' => (next, String::new()),
+                _ => (String::new(), next),
+            };
+            nodes.push((path[i].clone(), false, left, right));
+        }
+        // remembers the start, to copy its turns later.
+        nodes.push((path[len].clone(), true, path[0].clone(), String::new()));
+    }
+
+    let targets = nodes.iter().map(|node| node.0.clone()).collect_vec();
+    for (_, _, left, right) in nodes.iter_mut().filter(|node| !node.1) {
+        match left.is_empty() {
+            true => *left = rng.choose(&targets).clone(),
+            false => *right = rng.choose(&targets).clone(),
+        }
+    }
+    let turns = nodes
+        .iter()
+        .map(|node| (node.0.clone(), (node.2.clone(), node.3.clone())))
+        .collect::<HashMap<_, _>>();
+    for (_, _, left, right) in nodes.iter_mut().filter(|node| node.1) {
+        (*left, *right) = turns[left].clone();
+    }
+    rng.shuffle(&mut nodes);
+    let network = nodes
+        .iter()
+        .map(|(name, _, left, right)| format!("{name} = ({left}, {right})"))
+        .join("\n");
+    format!("{instructions}\n\n{network}")
+}
+
+/// Six walks with distinct prime cycle counts and a prime number of instructions, like the
+/// real inputs, but with shorter cycles: about 10,000 nodes.
+pub fn full(rng: &mut Rng) -> String {
+    let instructions = *rng.choose(&[53, 59, 61, 67]);
+    let mut cycles = vec![17, 19, 23, 29, 31, 37];
+    rng.shuffle(&mut cycles);
+    input(rng, instructions, &cycles)
+}
+
+/// A random name ending in `last`, or in neither `A` nor `Z`.
+fn unused_name(rng: &mut Rng, names: &mut HashSet<String>, last: Option<char>) -> String {
+    let letter = |rng: &mut Rng| char::from(rng.range(b'B'..=b'Y'));
+    loop {
+        let last = last.unwrap_or_else(|| letter(rng));
+        let name = format!("{}{}{last}", letter(rng), letter(rng));
+        if names.insert(name.clone()) {
+            return name;
+        }
+    }
+}
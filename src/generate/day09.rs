@@ -0,0 +1,27 @@
+//! Sequences of sensor readings.
+use std::ops::RangeInclusive;
+
+use itertools::Itertools;
+
+use crate::prop::Rng;
+
+/// `lines` sequences, each with a length in `lengths`, of values of random polynomials.
+/// Polynomials have fewer coefficients than values, so the differences always reach zero.
+///
+/// # Panics
+/// If a sequence can be shorter than 2 values.
+pub fn input(rng: &mut Rng, lines: usize, lengths: RangeInclusive<usize>) -> String {
+    assert!(*lengths.start() >= 2, "sequences need at least 2 values");
+    (0..lines)
+        .map(|_| {
+            let len = rng.range(lengths.clone());
+            let coefficients = (0..rng.range(1..len.min(8)))
+                .map(|_| rng.range(-9..=9i64))
+                .collect_vec();
+            let start = rng.range(-10..=10i64);
+            (start..start + len as i64)
+                .map(|x| coefficients.iter().rev().fold(0, |acc, c| acc * x + c))
+                .join(" ")
+        })
+        .join("\n")
+}
@@ -0,0 +1,170 @@
+//! Pipe mazes with a single loop.
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::prop::Rng;
+
+/// A generated maze with its answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeMaze {
+    pub input: String,
+    /// The `(x, y)` tiles of the loop, in order.
+    pub loop_tiles: Vec<(usize, usize)>,
+    /// The number of steps to the farthest point of the loop.
+    pub farthest: usize,
+    /// The number of tiles enclosed by the loop.
+    pub enclosed: usize,
+}
+
+/// A loop around a random blob of up to `cells` cells in a `width` x `height` area, between
+/// junk pipes. The blob never touches itself diagonally and has no holes, so the corners of
+/// its cells' outline form a simple loop, which encloses exactly the corners surrounded by
+/// four blob cells. Corners become tiles: the map is about one tile larger than the area.
+///
+/// The blob prefers to grow at its tips, which makes for long, winding loops.
+///
+/// # Panics
+/// If the area is empty.
+pub fn input(rng: &mut Rng, width: usize, height: usize, cells: usize) -> PipeMaze {
+    // a ring of empty cells avoids bounds checks.
+    let mut blob = vec![vec![false; width + 2]; height + 2];
+    let mut frontier = vec![(rng.range(1..=width), rng.range(1..=height))];
+    let mut grown = 0;
+    while grown < cells && !frontier.is_empty() {
+        let (x, y) = frontier.swap_remove(rng.range(0..frontier.len()));
+        // cells that can't grow yet are added again when a neighbour joins.
+        if blob[y][x] || (grown > 0 && !can_grow(&blob, x, y)) {
+            continue;
+        }
+        let sides = [(x, y - 1), (x, y + 1), (x - 1, y), (x + 1, y)];
+        if sides.iter().filter(|&&(x, y)| blob[y][x]).count() > 1 && !rng.bool(0.1) {
+            frontier.push((x, y));
+            continue;
+        }
+        blob[y][x] = true;
+        grown += 1;
+        frontier.extend(
+            sides
+                .into_iter()
+                .filter(|&(x, y)| (1..=width).contains(&x) && (1..=height).contains(&y)),
+        );
+    }
+
+    // outline edges, clockwise around every cell, between corners `(x, y)` of cells.
+    let mut next = HashMap::new();
+    for (y, x) in (1..=height).cartesian_product(1..=width) {
+        if blob[y][x] {
+            let sides = [
+                (blob[y - 1][x], (x, y), (x + 1, y)),
+                (blob[y][x + 1], (x + 1, y), (x + 1, y + 1)),
+                (blob[y + 1][x], (x + 1, y + 1), (x, y + 1)),
+                (blob[y][x - 1], (x, y + 1), (x, y)),
+            ];
+            for (neighbour, from, to) in sides {
+                if !neighbour {
+                    next.insert(from, to);
+                }
+            }
+        }
+    }
+    let start = *next.keys().min().unwrap();
+    let mut outline = vec![start];
+    while next[outline.last().unwrap()] != start {
+        outline.push(next[outline.last().unwrap()]);
+    }
+
+    // cropped to the loop plus a random margin.
+    let xs = outline.iter().map(|c| c.0).minmax().into_option().unwrap();
+    let ys = outline.iter().map(|c| c.1).minmax().into_option().unwrap();
+    let (x0, y0) = (xs.0 - rng.range(0..=1), ys.0 - rng.range(0..=1));
+    let (w, h) = (
+        xs.1 + rng.range(0..=1) + 1 - x0,
+        ys.1 + rng.range(0..=1) + 1 - y0,
+    );
+
+    let mut map = (0..h)
+        .map(|_| {
+            (0..w)
+                .map(|_| match rng.bool(0.5) {
+                    true => '.',
+                    false => *rng.choose(&['|', '-', 'L', 'J', '7', 'F']),
+                })
+                .collect_vec()
+        })
+        .collect_vec();
+    for (i, &(x, y)) in outline.iter().enumerate() {
+        let prev = outline[(i + outline.len() - 1) % outline.len()];
+        let next = outline[(i + 1) % outline.len()];
+        let arm = |(nx, ny): (usize, usize)| match (nx.cmp(&x), ny.cmp(&y)) {
+            (_, std::cmp::Ordering::Less) => 'N',
+            (std::cmp::Ordering::Greater, _) => 'E',
+            (_, std::cmp::Ordering::Greater) => 'S',
+            _ => 'W',
+        };
+        let mut arms = [arm(prev), arm(next)];
+        arms.sort();
+        map[y - y0][x - x0] = match arms {
+            ['N', 'S'] => '|',
+            ['E', 'W'] => '-',
+            ['E', 'N'] => 'L',
+            ['N', 'W'] => 'J',
+            ['S', 'W'] => '7',
+            _ => 'F',
+        };
+    }
+    let (sx, sy) = *rng.choose(&outline);
+    let (sx, sy) = (sx - x0, sy - y0);
+    map[sy][sx] = 'S';
+    // junk pipes next to 'S' must not look connected to it.
+    let around = [
+        (sx + 1, sy),
+        (sx.wrapping_sub(1), sy),
+        (sx, sy + 1),
+        (sx, sy.wrapping_sub(1)),
+    ];
+    for (nx, ny) in around {
+        if let Some(tile) = map.get_mut(ny).and_then(|row| row.get_mut(nx)) {
+            if !outline.contains(&(nx + x0, ny + y0)) {
+                *tile = '.';
+            }
+        }
+    }
+
+    let enclosed = (y0..y0 + h)
+        .cartesian_product(x0..x0 + w)
+        .filter(|&(y, x)| x > 0 && y > 0)
+        .filter(|&(y, x)| blob[y - 1][x - 1] && blob[y - 1][x] && blob[y][x - 1] && blob[y][x])
+        .count();
+    PipeMaze {
+        input: map
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .join("\n"),
+        loop_tiles: outline.iter().map(|&(x, y)| (x - x0, y - y0)).collect(),
+        farthest: outline.len() / 2,
+        enclosed,
+    }
+}
+
+/// Whether a cell can join the blob without enclosing a hole or touching the blob only
+/// diagonally: its neighbours in the blob, walking around it, must form a single run that
+/// includes a side neighbour.
+fn can_grow(blob: &[Vec<bool>], x: usize, y: usize) -> bool {
+    const AROUND: [(isize, isize); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+    let inside = AROUND.map(|(dx, dy)| blob[y.wrapping_add_signed(dy)][x.wrapping_add_signed(dx)]);
+    let runs = (0..8)
+        .filter(|&i| inside[i] && !inside[(i + 7) % 8])
+        .count();
+    let side = (0..8).step_by(2).any(|i| inside[i]);
+    runs == 1 && side
+}
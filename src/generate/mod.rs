@@ -0,0 +1,66 @@
+//! Seeded generators of puzzle inputs with the structure of the real ones, so that solutions
+//! can be run and benchmarked without personal inputs (see `cargo generate`).
+//!
+//! Every day's generator takes explicit size parameters: [`full_input`] picks ones like the
+//! real inputs', the property tests in `src/bin` pick small ones that naive oracles can handle.
+use crate::prop::Rng;
+use crate::Day;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+
+/// Whether there's a generator for `day`.
+pub fn has_generator(day: Day) -> bool {
+    day <= 10
+}
+
+/// Generates a full-size input for `day` from `seed`, [`None`] if there's no generator for it.
+pub fn full_input(day: Day, seed: u64) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+    let input = match day.into_inner() {
+        1 => day01::input(rng, 1000, 12),
+        2 => day02::input(rng, 100, 6),
+        3 => day03::input(rng, 140, 140),
+        4 => day04::input(rng, 200, 10, 25),
+        5 => day05::input(rng, 1 << 32, 40, 10, 1 << 27),
+        6 => day06::input(rng, 4, 99),
+        7 => day07::input(rng, 1000),
+        8 => day08::full(rng),
+        9 => day09::input(rng, 200, 21..=21),
+        10 => day10::input(rng, 139, 139, 139 * 139 / 2).input,
+        _ => return None,
+    };
+    Some(input)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::all_days;
+
+    #[test]
+    fn full_inputs_are_deterministic() {
+        for day in all_days() {
+            let input = full_input(day, 7);
+            assert_eq!(input.is_some(), has_generator(day));
+            if let Some(input) = input {
+                assert!(!input.is_empty() && !input.ends_with('\n'), "day {day}");
+                assert_eq!(full_input(day, 7), Some(input), "day {day}");
+            }
+        }
+        assert_ne!(
+            full_input(Day::new(1).unwrap(), 8),
+            full_input(Day::new(1).unwrap(), 7)
+        );
+    }
+}
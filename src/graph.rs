@@ -0,0 +1,434 @@
+//! Generic graph searches over implicit graphs.
+//!
+//! Nodes can be any hashable value (grid coordinates, node-table keys, ...) and edges are
+//! described by a neighbour function, so no adjacency structure has to be built up front.
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Distances and parent links produced by [`bfs`] and [`dijkstra`].
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C> {
+    pub distances: FxHashMap<N, C>,
+    pub parents: FxHashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> SearchResult<N, C> {
+    /// Returns the distance from the start to `node`, if it was reached.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// Reconstructs the path from the start to `goal` (both inclusive), if `goal` was reached.
+    pub fn path_to(&self, goal: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(goal) {
+            return None;
+        }
+        Some(reconstruct_path(&self.parents, goal.clone()))
+    }
+}
+
+fn reconstruct_path<N: Clone + Eq + Hash>(parents: &FxHashMap<N, N>, goal: N) -> Vec<N> {
+    let mut path = vec![goal];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Breadth-first search from `start`, where every edge has a cost of 1.
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> SearchResult<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = FxHashMap::default();
+    let mut parents = FxHashMap::default();
+    let mut queue = VecDeque::from([start.clone()]);
+    distances.insert(start, 0);
+
+    while let Some(node) = queue.pop_front() {
+        let dist = distances[&node];
+        for next in neighbours(&node) {
+            if distances.contains_key(&next) {
+                continue;
+            }
+            distances.insert(next.clone(), dist + 1);
+            parents.insert(next.clone(), node.clone());
+            queue.push_back(next);
+        }
+    }
+
+    SearchResult { distances, parents }
+}
+
+/// Depth-first search from `start`. Returns nodes in the order they were first visited.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = FxHashSet::default();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        let mut next = neighbours(&node)
+            .into_iter()
+            .filter(|n| !seen.contains(n))
+            .collect::<Vec<_>>();
+        // push in reverse so the first neighbour is visited first.
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+
+    order
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Entry of the priority queue, ordered by (reversed) priority only.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Dijkstra's shortest paths from `start`. `neighbours` yields `(node, edge_cost)` pairs.
+/// `C::default()` is used as the zero cost.
+pub fn dijkstra<N, C, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut distances = FxHashMap::default();
+    let mut parents = FxHashMap::default();
+    let mut heap = BinaryHeap::new();
+    distances.insert(start.clone(), C::default());
+    heap.push(Queued {
+        priority: C::default(),
+        cost: C::default(),
+        node: start,
+    });
+
+    while let Some(Queued { cost, node, .. }) = heap.pop() {
+        if distances.get(&node).is_some_and(|&d| cost > d) {
+            continue;
+        }
+        for (next, edge) in neighbours(&node) {
+            let next_cost = cost + edge;
+            if distances.get(&next).is_some_and(|&d| d <= next_cost) {
+                continue;
+            }
+            distances.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), node.clone());
+            heap.push(Queued {
+                priority: next_cost,
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+
+    SearchResult { distances, parents }
+}
+
+/// A* search from `start` to the first node satisfying `is_goal`.
+/// `heuristic` must never overestimate the remaining cost for the result to be optimal.
+/// Returns the path (start and goal inclusive) and its total cost.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut distances = FxHashMap::default();
+    let mut parents = FxHashMap::default();
+    let mut heap = BinaryHeap::new();
+    distances.insert(start.clone(), C::default());
+    heap.push(Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    });
+
+    while let Some(Queued { cost, node, .. }) = heap.pop() {
+        if is_goal(&node) {
+            return Some((reconstruct_path(&parents, node), cost));
+        }
+        if distances.get(&node).is_some_and(|&d| cost > d) {
+            continue;
+        }
+        for (next, edge) in neighbours(&node) {
+            let next_cost = cost + edge;
+            if distances.get(&next).is_some_and(|&d| d <= next_cost) {
+                continue;
+            }
+            distances.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), node.clone());
+            heap.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Orders `nodes` so that every node comes before its successors (Kahn's algorithm).
+/// Successors that are not part of `nodes` are added to the graph.
+/// Returns [`None`] if the graph contains a cycle.
+pub fn topological_sort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut nodes_seen: Vec<N> = Vec::new();
+    let mut edges: FxHashMap<N, Vec<N>> = FxHashMap::default();
+    let mut in_degree: FxHashMap<N, usize> = FxHashMap::default();
+    let mut pending: VecDeque<N> = nodes.into_iter().collect();
+
+    while let Some(node) = pending.pop_front() {
+        if edges.contains_key(&node) {
+            continue;
+        }
+        in_degree.entry(node.clone()).or_insert(0);
+        let next = successors(&node).into_iter().collect::<Vec<_>>();
+        for n in &next {
+            *in_degree.entry(n.clone()).or_insert(0) += 1;
+            if !edges.contains_key(n) {
+                pending.push_back(n.clone());
+            }
+        }
+        nodes_seen.push(node.clone());
+        edges.insert(node, next);
+    }
+
+    let mut queue: VecDeque<N> = nodes_seen
+        .iter()
+        .filter(|n| in_degree[*n] == 0)
+        .cloned()
+        .collect();
+    let mut sorted = Vec::with_capacity(nodes_seen.len());
+
+    while let Some(node) = queue.pop_front() {
+        for next in &edges[&node] {
+            let degree = in_degree.get_mut(next).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                queue.push_back(next.clone());
+            }
+        }
+        sorted.push(node);
+    }
+
+    (sorted.len() == nodes_seen.len()).then_some(sorted)
+}
+
+/// Groups `nodes` into connected components. `neighbours` is expected to be symmetric.
+/// Components are returned in the order their first node appears in `nodes`.
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = FxHashSet::default();
+    let mut components = Vec::new();
+
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component = dfs(node, &mut neighbours);
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+
+    components
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Yields the orthogonal neighbours of `(x, y)` that lie inside `grid`.
+pub fn grid_neighbours<T>(
+    grid: &[Vec<T>],
+    (x, y): (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    [(0, -1), (1, 0), (0, 1), (-1, 0)]
+        .into_iter()
+        .filter_map(move |(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+            grid.get(ny)?.get(nx)?;
+            Some((nx, ny))
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn parse_grid(s: &str) -> Vec<Vec<char>> {
+        s.lines().map(|l| l.chars().collect()).collect()
+    }
+
+    const MAZE: &str = "S.#.\n..#.\n.#..\n...E";
+
+    fn open_neighbours(grid: &[Vec<char>], pos: (usize, usize)) -> Vec<(usize, usize)> {
+        grid_neighbours(grid, pos)
+            .filter(|&(x, y)| grid[y][x] != '#')
+            .collect()
+    }
+
+    #[test]
+    fn bfs_grid_distances_and_path() {
+        let grid = parse_grid(MAZE);
+        let res = bfs((0, 0), |&p| open_neighbours(&grid, p));
+        assert_eq!(res.distance(&(3, 3)), Some(6));
+        assert_eq!(res.distance(&(3, 0)), Some(9));
+        let path = res.path_to(&(3, 3)).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(3, 3)));
+        assert!(res.path_to(&(2, 0)).is_none());
+    }
+
+    #[test]
+    fn bfs_node_table() {
+        let table: FxHashMap<&str, (&str, &str)> = [
+            ("AAA", ("BBB", "BBB")),
+            ("BBB", ("AAA", "ZZZ")),
+            ("ZZZ", ("ZZZ", "ZZZ")),
+        ]
+        .into_iter()
+        .collect();
+        let res = bfs("AAA", |n| {
+            let (l, r) = table[n];
+            [l, r]
+        });
+        assert_eq!(res.distance(&"ZZZ"), Some(2));
+        assert_eq!(res.path_to(&"ZZZ").unwrap(), vec!["AAA", "BBB", "ZZZ"]);
+    }
+
+    #[test]
+    fn dijkstra_weighted() {
+        let edges = |n: &u8| -> Vec<(u8, u32)> {
+            match n {
+                0 => vec![(1, 4), (2, 1)],
+                2 => vec![(1, 2), (3, 7)],
+                1 => vec![(3, 1)],
+                _ => vec![],
+            }
+        };
+        let res = dijkstra(0, edges);
+        assert_eq!(res.distance(&1), Some(3));
+        assert_eq!(res.distance(&3), Some(4));
+        assert_eq!(res.path_to(&3).unwrap(), vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn astar_matches_bfs() {
+        let grid = parse_grid(MAZE);
+        let (path, cost) = astar(
+            (0, 0),
+            |&p| open_neighbours(&grid, p).into_iter().map(|n| (n, 1)),
+            |&(x, y)| 3 - x + 3 - y,
+            |&p| p == (3, 3),
+        )
+        .unwrap();
+        assert_eq!(cost, 6);
+        assert_eq!(path.len(), 7);
+        assert!(astar((0, 0), |_| [((1, 1), 1)], |_| 0, |&p| p == (9, 9)).is_none());
+    }
+
+    #[test]
+    fn dfs_visits_reachable() {
+        let order = dfs(1, |&n: &u32| {
+            if n < 4 {
+                vec![n * 2, n * 2 + 1]
+            } else {
+                vec![]
+            }
+        });
+        assert_eq!(order, vec![1, 2, 4, 5, 3, 6, 7]);
+    }
+
+    #[test]
+    fn topological_sort_and_cycles() {
+        let dag = |n: &char| -> Vec<char> {
+            match n {
+                'a' => vec!['b', 'c'],
+                'b' => vec!['d'],
+                'c' => vec!['d'],
+                _ => vec![],
+            }
+        };
+        let sorted = topological_sort(['a'], dag).unwrap();
+        let pos = |c| sorted.iter().position(|&x| x == c).unwrap();
+        assert_eq!(sorted.len(), 4);
+        assert!(pos('a') < pos('b') && pos('b') < pos('d') && pos('c') < pos('d'));
+
+        assert!(topological_sort([1], |&n: &u8| [(n + 1) % 3]).is_none());
+    }
+
+    #[test]
+    fn connected_components_grid() {
+        let grid = parse_grid("aa.b\n...b\ncc.b");
+        let cells = (0..3).flat_map(|y| (0..4).map(move |x| (x, y)));
+        let components = connected_components(cells.filter(|&(x, y)| grid[y][x] != '.'), |&p| {
+            grid_neighbours(&grid, p)
+                .filter(|&(x, y)| grid[y][x] == grid[p.1][p.0])
+                .collect::<Vec<_>>()
+        });
+        let sizes = components.iter().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(sizes, vec![2, 3, 2]);
+    }
+}
@@ -0,0 +1,275 @@
+//! Classification of card hands (as in 2023 day 7) into sortable keys.
+//!
+//! A hand's type is determined by the sizes of its groups of equal cards, largest first:
+//! `[5]` (five of a kind) beats `[4, 1]`, which beats `[3, 2]`, ... down to `[1, 1, 1, 1, 1]`.
+//! Wildcards join the largest group. Hands of the same type are ordered card by card.
+//!
+//! ```
+//! # use advent_of_code::hands::HandRules;
+//! let jokers = HandRules::new("J23456789TQKA").with_wildcards("J");
+//! assert!(jokers.key("JJJJ2").unwrap() > jokers.key("QQQQ2").unwrap());
+//! ```
+use std::error::Error;
+use std::fmt::Display;
+
+/// An error which can be returned when classifying a hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandError {
+    UnknownCard(char),
+    WrongSize {
+        expected: usize,
+        found: usize,
+    },
+    /// The rules allow hands whose key doesn't fit into a `u64`.
+    KeyOverflow,
+}
+
+impl Error for HandError {}
+
+impl Display for HandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandError::UnknownCard(c) => write!(f, "unknown card {c:?}"),
+            HandError::WrongSize { expected, found } => {
+                write!(f, "expected {expected} cards, found {found}")
+            }
+            HandError::KeyOverflow => f.write_str("hand key does not fit into a u64"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Describes how hands are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandRules<'a> {
+    order: &'a str,
+    wildcards: &'a str,
+    hand_size: usize,
+}
+
+impl<'a> HandRules<'a> {
+    /// Five card hands without wildcards, with cards ranked by `order` from weakest to strongest.
+    pub const fn new(order: &'a str) -> Self {
+        Self {
+            order,
+            wildcards: "",
+            hand_size: 5,
+        }
+    }
+
+    /// Makes every card in `wildcards` act like whichever card gives the strongest hand type.
+    /// For ties, wildcards keep their position in the card order.
+    pub const fn with_wildcards(self, wildcards: &'a str) -> Self {
+        Self { wildcards, ..self }
+    }
+
+    pub const fn with_hand_size(self, hand_size: usize) -> Self {
+        Self { hand_size, ..self }
+    }
+
+    fn card_index(&self, card: char) -> Result<u64, HandError> {
+        self.order
+            .chars()
+            .position(|c| c == card)
+            .map(|i| i as u64)
+            .ok_or(HandError::UnknownCard(card))
+    }
+
+    /// Returns the group sizes of a hand, largest first, after assigning the wildcards.
+    pub fn groups(&self, cards: &str) -> Result<Vec<usize>, HandError> {
+        let found = cards.chars().count();
+        if found != self.hand_size {
+            return Err(HandError::WrongSize {
+                expected: self.hand_size,
+                found,
+            });
+        }
+
+        let mut counts = vec![0; self.order.chars().count()];
+        let mut wild = 0;
+        for card in cards.chars() {
+            let index = self.card_index(card)?;
+            if self.wildcards.contains(card) {
+                wild += 1;
+            } else {
+                counts[index as usize] += 1;
+            }
+        }
+
+        let mut groups = counts.into_iter().filter(|&n| n > 0).collect::<Vec<_>>();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        match groups.first_mut() {
+            Some(largest) => *largest += wild,
+            None => groups.push(wild),
+        }
+        Ok(groups)
+    }
+
+    /// Encodes a hand into a key, such that stronger hands have larger keys.
+    pub fn key(&self, cards: &str) -> Result<u64, HandError> {
+        let groups = self.groups(cards)?;
+        let group_base = self.hand_size as u64 + 1;
+        let card_base = self.order.chars().count() as u64;
+
+        let push = |key: u64, base: u64, digit: u64| {
+            key.checked_mul(base)
+                .and_then(|k| k.checked_add(digit))
+                .ok_or(HandError::KeyOverflow)
+        };
+
+        let mut key = 0;
+        for i in 0..self.hand_size {
+            key = push(key, group_base, groups.get(i).copied().unwrap_or(0) as u64)?;
+        }
+        for card in cards.chars() {
+            key = push(key, card_base, self.card_index(card)?)?;
+        }
+        Ok(key)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    const ORDER: &str = "23456789TJQKA";
+    const JOKER_ORDER: &str = "J23456789TQKA";
+
+    /// Day 7's original hand types: 0 (high card) to 6 (five of a kind).
+    fn hand_type(cards: &[char]) -> u8 {
+        let counts = cards.iter().counts();
+        match counts.len() {
+            1 => 6,
+            2 if counts.values().contains(&4) => 5,
+            2 => 4,
+            3 if counts.values().contains(&3) => 3,
+            3 => 2,
+            4 => 1,
+            _ => 0,
+        }
+    }
+
+    /// The best hand type over every substitution of the wildcards.
+    ///
+    /// Wildcards only need to try the natural cards in the hand and one card that isn't:
+    /// any two absent cards are interchangeable, and using the same one never lowers the type.
+    fn best_type(cards: &[char], order: &str, wildcards: &str) -> u8 {
+        let natural = |c: &char| !wildcards.contains(*c);
+        let mut targets = cards.iter().copied().filter(natural).unique().collect_vec();
+        targets.extend(order.chars().filter(natural).find(|c| !cards.contains(c)));
+
+        let options = cards
+            .iter()
+            .map(|c| {
+                if natural(c) {
+                    vec![*c]
+                } else {
+                    targets.clone()
+                }
+            })
+            .collect_vec();
+        options
+            .into_iter()
+            .multi_cartesian_product()
+            .map(|hand| hand_type(&hand))
+            .max()
+            .unwrap()
+    }
+
+    fn multisets(order: &str, size: usize) -> impl Iterator<Item = String> + '_ {
+        order
+            .chars()
+            .combinations_with_replacement(size)
+            .map(|hand| hand.into_iter().collect())
+    }
+
+    /// Checks every 5-card multiset against the oracle, and that keys sort like the oracle does.
+    fn check_five_card_rules(order: &str, wildcards: &str) {
+        let rules = HandRules::new(order).with_wildcards(wildcards);
+        let card_value = |c: char| order.find(c).unwrap();
+
+        let mut hands = multisets(order, 5)
+            .map(|hand| {
+                let chars = hand.chars().collect_vec();
+                let oracle = (
+                    best_type(&chars, order, wildcards),
+                    chars.iter().map(|&c| card_value(c)).collect_vec(),
+                );
+                (oracle, rules.key(&hand).unwrap(), hand)
+            })
+            .collect_vec();
+        assert_eq!(hands.len(), 6188);
+
+        hands.sort_by(|a, b| a.0.cmp(&b.0));
+        for (lower, higher) in hands.iter().tuple_windows() {
+            assert!(
+                lower.1 < higher.1,
+                "{} should rank below {} with wildcards {wildcards:?}",
+                lower.2,
+                higher.2
+            );
+        }
+    }
+
+    #[test]
+    fn exhaustive_without_wildcards() {
+        check_five_card_rules(ORDER, "");
+    }
+
+    #[test]
+    fn exhaustive_with_jokers() {
+        check_five_card_rules(JOKER_ORDER, "J");
+    }
+
+    #[test]
+    fn exhaustive_with_several_wildcards() {
+        check_five_card_rules(JOKER_ORDER, "J2");
+        check_five_card_rules("23456789TJQKA", "AKQ");
+    }
+
+    #[test]
+    fn other_hand_sizes() {
+        for size in [1, 3, 6] {
+            let rules = HandRules::new(JOKER_ORDER)
+                .with_wildcards("J")
+                .with_hand_size(size);
+            for hand in multisets(JOKER_ORDER, size) {
+                let groups = rules.groups(&hand).unwrap();
+                assert_eq!(groups.iter().sum::<usize>(), size);
+                let jokers = hand.matches('J').count();
+                let largest_natural = hand
+                    .chars()
+                    .filter(|&c| c != 'J')
+                    .counts()
+                    .into_values()
+                    .max()
+                    .unwrap_or(0);
+                assert_eq!(groups[0], largest_natural + jokers, "{hand}");
+            }
+        }
+        // four of a kind beats two triples, which beat a full house.
+        let six = HandRules::new(ORDER).with_hand_size(6);
+        assert!(six.key("222234").unwrap() > six.key("222333").unwrap());
+        assert!(six.key("222333").unwrap() > six.key("AAAKKQ").unwrap());
+    }
+
+    #[test]
+    fn reports_errors() {
+        let rules = HandRules::new(ORDER);
+        assert_eq!(rules.key("2345X"), Err(HandError::UnknownCard('X')));
+        assert_eq!(
+            rules.key("2345"),
+            Err(HandError::WrongSize {
+                expected: 5,
+                found: 4
+            })
+        );
+        let huge = HandRules::new(ORDER).with_hand_size(20);
+        assert_eq!(huge.key(&"A".repeat(20)), Err(HandError::KeyOverflow));
+    }
+}
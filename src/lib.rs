@@ -1,4 +1,18 @@
+pub mod bitset;
 mod day;
+pub mod generate;
+pub mod graph;
+pub mod hands;
+pub mod math;
+pub mod memo;
+pub mod multi_pattern;
+pub mod ocr;
+pub mod parse;
+pub mod pattern;
+pub mod polygon;
+pub mod prop;
+pub mod sequences;
 pub mod template;
+pub mod viz;
 
 pub use day::*;
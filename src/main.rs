@@ -1,26 +1,65 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, bench, decrypt, download, encrypt, examples, generate, keygen, read, reset, scaffold,
+    solve,
+};
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
 
+    use advent_of_code::template::commands::examples::Pick;
+    use advent_of_code::template::commands::reset::ResetArgs;
+    use advent_of_code::template::commands::scaffold::TemplateArgs;
+    use advent_of_code::template::commands::solve::{InputSource, RunArgs, VizArgs};
     use advent_of_code::Day;
 
     pub enum AppArguments {
+        Bench {
+            day: Day,
+            compare_variants: bool,
+        },
+        Decrypt {
+            day: Option<Day>,
+            force: bool,
+        },
         Download {
             day: Day,
         },
+        Encrypt {
+            day: Option<Day>,
+            force: bool,
+        },
+        ExtractExamples {
+            day: Day,
+            picks: Vec<Pick>,
+            force: bool,
+        },
+        Generate {
+            day: Option<Day>,
+            seed: Option<u64>,
+            force: bool,
+        },
+        Keygen {
+            force: bool,
+        },
         Read {
             day: Day,
         },
+        Reset {
+            day: Day,
+            args: ResetArgs,
+        },
         Scaffold {
             day: Day,
+            template: TemplateArgs,
         },
         Solve {
             day: Day,
             release: bool,
-            time: bool,
+            run: RunArgs,
+            viz: VizArgs,
             submit: Option<u8>,
+            input: InputSource,
         },
         All {
             release: bool,
@@ -31,25 +70,104 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let mut app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
             },
+            Some("bench") => AppArguments::Bench {
+                // the alias puts the flag before the day.
+                compare_variants: args.contains("--compare-variants"),
+                day: args.free_from_str()?,
+            },
+            Some("decrypt") => AppArguments::Decrypt {
+                day: match args.contains("--all") {
+                    true => None,
+                    false => Some(args.free_from_str()?),
+                },
+                force: args.contains("--force"),
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
+            Some("encrypt") => AppArguments::Encrypt {
+                day: match args.contains("--all") {
+                    true => None,
+                    false => Some(args.free_from_str()?),
+                },
+                force: args.contains("--force"),
+            },
+            Some("examples") => match args.subcommand()?.as_deref() {
+                Some("extract") => AppArguments::ExtractExamples {
+                    day: args.free_from_str()?,
+                    picks: args.values_from_str("--pick")?,
+                    force: args.contains("--force"),
+                },
+                _ => {
+                    eprintln!("Unknown examples command, try \"examples extract <day>\".");
+                    process::exit(1);
+                }
+            },
+            Some("generate") => AppArguments::Generate {
+                day: match args.contains("--all") {
+                    true => None,
+                    false => Some(args.free_from_str()?),
+                },
+                seed: args.opt_value_from_str("--seed")?,
+                force: args.contains("--force"),
+            },
+            Some("keygen") => AppArguments::Keygen {
+                force: args.contains("--force"),
+            },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("reset") => AppArguments::Reset {
+                day: args.free_from_str()?,
+                args: ResetArgs {
+                    dry_run: args.contains("--dry-run"),
+                    input: args.contains("--input"),
+                    template: TemplateArgs {
+                        template: args.opt_value_from_str("--template")?,
+                        answer_types: args.opt_value_from_str("--answer")?,
+                        imports: args.opt_value_from_str("--use")?,
+                    },
+                },
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
+                template: TemplateArgs {
+                    template: args.opt_value_from_str("--template")?,
+                    answer_types: args.opt_value_from_str("--answer")?,
+                    imports: args.opt_value_from_str("--use")?,
+                },
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                input: match (
+                    args.opt_value_from_str("--input")?,
+                    args.contains("--example"),
+                ) {
+                    (Some(_), true) => {
+                        eprintln!("Pass either --input or --example, not both.");
+                        process::exit(1);
+                    }
+                    (Some(path), false) => InputSource::Path(path),
+                    (None, true) => InputSource::Examples(None),
+                    (None, false) => InputSource::Day,
+                },
+                run: RunArgs {
+                    time: args.contains("--time"),
+                    trace: args.contains("--trace"),
+                    variant: args.opt_value_from_str("--variant")?,
+                },
+                viz: VizArgs {
+                    enabled: args.contains("--viz"),
+                    out: args.opt_value_from_str("--viz-out")?,
+                    delay_ms: args.opt_value_from_str("--viz-delay")?,
+                },
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -61,7 +179,22 @@ mod args {
             }
         };
 
-        let remaining = args.finish();
+        let mut remaining = args.finish();
+
+        // the example's name is optional, so it's taken from what's left after all flags.
+        if let AppArguments::Solve {
+            input: InputSource::Examples(name),
+            ..
+        } = &mut app_args
+        {
+            if let Some(i) = remaining
+                .iter()
+                .position(|arg| !arg.to_string_lossy().starts_with('-'))
+            {
+                *name = Some(remaining.remove(i).to_string_lossy().into_owned());
+            }
+        }
+
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
@@ -78,15 +211,29 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::Decrypt { day, force } => decrypt::handle(day, force),
             AppArguments::Download { day } => download::handle(day),
+            AppArguments::Encrypt { day, force } => encrypt::handle(day, force),
+            AppArguments::ExtractExamples { day, picks, force } => {
+                examples::handle_extract(day, &picks, force);
+            }
+            AppArguments::Generate { day, seed, force } => generate::handle(day, seed, force),
+            AppArguments::Keygen { force } => keygen::handle(force),
             AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::Reset { day, args } => reset::handle(day, &args),
+            AppArguments::Scaffold { day, template } => scaffold::handle(day, &template),
             AppArguments::Solve {
                 day,
                 release,
-                time,
+                run,
+                viz,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                input,
+            } => solve::handle(day, release, run, viz, submit, input),
+            AppArguments::Bench {
+                day,
+                compare_variants,
+            } => bench::handle(day, compare_variants),
         },
     };
 }
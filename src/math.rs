@@ -0,0 +1,153 @@
+//! Integer helpers: binary search over monotone predicates and exact quadratic intervals.
+use std::ops::RangeInclusive;
+
+/// Primitive integers that can be binary searched.
+pub trait SearchInt: Copy + Ord {
+    /// The floor of `(a + b) / 2`, computed without overflowing.
+    fn midpoint_floor(a: Self, b: Self) -> Self;
+    fn step_up(self) -> Self;
+    fn step_down(self) -> Self;
+}
+
+macro_rules! impl_search_int {
+    ($($t:ty),*) => {$(
+        impl SearchInt for $t {
+            fn midpoint_floor(a: Self, b: Self) -> Self {
+                (a & b) + ((a ^ b) >> 1)
+            }
+            fn step_up(self) -> Self {
+                self + 1
+            }
+            fn step_down(self) -> Self {
+                self - 1
+            }
+        }
+    )*};
+}
+
+impl_search_int!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+/// Returns the smallest `x` in `lo..=hi` for which `pred(x)` holds.
+/// `pred` must be monotone over the range, i.e. `false` up to some point and `true` after it.
+/// Returns [`None`] if `pred` doesn't hold anywhere in the range.
+pub fn first_true<T: SearchInt>(
+    mut lo: T,
+    mut hi: T,
+    mut pred: impl FnMut(T) -> bool,
+) -> Option<T> {
+    if lo > hi || !pred(hi) {
+        return None;
+    }
+    while lo < hi {
+        let mid = T::midpoint_floor(lo, hi);
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid.step_up();
+        }
+    }
+    Some(lo)
+}
+
+/// Returns the largest `x` in `lo..=hi` for which `pred(x)` holds.
+/// `pred` must be monotone over the range, i.e. `true` up to some point and `false` after it.
+/// Returns [`None`] if `pred` doesn't hold anywhere in the range.
+pub fn last_true<T: SearchInt>(lo: T, hi: T, mut pred: impl FnMut(T) -> bool) -> Option<T> {
+    if lo > hi || !pred(lo) {
+        return None;
+    }
+    match first_true(lo, hi, |x| !pred(x)) {
+        Some(x) => Some(x.step_down()),
+        None => Some(hi),
+    }
+}
+
+/// Floor of the square root of `n`.
+pub fn isqrt(n: u128) -> u128 {
+    let hi = n.min(u128::from(u64::MAX));
+    last_true(0, hi, |x| x * x <= n).unwrap_or(0)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the interval of integers `x` in `0..=total` for which `x * (total - x) > threshold`.
+///
+/// The bounds are the roots of `x^2 - total * x + threshold`, computed with an integer
+/// square root and then nudged onto the exact integer boundary, so large inputs don't
+/// suffer from floating point error.
+/// Returns [`None`] if no such `x` exists.
+pub fn product_above(total: u64, threshold: u64) -> Option<RangeInclusive<u64>> {
+    let (t, d) = (u128::from(total), u128::from(threshold));
+    let wins = |x: u128| x * (t - x) > d;
+
+    let discriminant = (t * t).checked_sub(4 * d)?;
+    let mut lo = (t - isqrt(discriminant)) / 2;
+    while lo <= t / 2 && !wins(lo) {
+        lo += 1;
+    }
+    while lo > 0 && wins(lo - 1) {
+        lo -= 1;
+    }
+    if lo > t / 2 || !wins(lo) {
+        return None;
+    }
+
+    // the parabola is symmetric around `total / 2`.
+    Some(lo as u64..=(t - lo) as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_search_bounds() {
+        assert_eq!(first_true(0u64, 100, |x| x * x >= 50), Some(8));
+        assert_eq!(first_true(0u64, 100, |_| false), None);
+        assert_eq!(first_true(5i64, 3, |_| true), None);
+        assert_eq!(first_true(i64::MIN, i64::MAX, |x| x >= -3), Some(-3));
+        assert_eq!(last_true(0u32, u32::MAX, |x| x <= 12345), Some(12345));
+        assert_eq!(last_true(0u32, 10, |_| true), Some(10));
+        assert_eq!(last_true(0u32, 10, |_| false), None);
+    }
+
+    #[test]
+    fn integer_square_roots() {
+        for n in 0..2000u128 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+        }
+        assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+    }
+
+    fn brute_force(total: u64, threshold: u64) -> Option<RangeInclusive<u64>> {
+        let wins = (0..=total)
+            .filter(|&x| x * (total - x) > threshold)
+            .collect::<Vec<_>>();
+        Some(*wins.first()?..=*wins.last()?)
+    }
+
+    #[test]
+    fn product_above_matches_brute_force() {
+        for total in 0..60 {
+            for threshold in 0..(total * total / 4 + 3) {
+                assert_eq!(
+                    product_above(total, threshold),
+                    brute_force(total, threshold),
+                    "total: {total}, threshold: {threshold}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn product_above_large_values() {
+        // the product peaks at (total / 2)^2 = 1 << 62, reached only at x = 1 << 31.
+        let total = 1u64 << 32;
+        let threshold = (1u64 << 62) - 1;
+        assert_eq!(product_above(total, threshold), Some(1 << 31..=1 << 31));
+        assert_eq!(product_above(total, 1 << 62), None);
+    }
+}
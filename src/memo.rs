@@ -0,0 +1,215 @@
+//! Memoization for recursive functions.
+//!
+//! When a solution runs with `--trace`, the runner logs the combined [`MemoStats`] of the memos
+//! each part used, see [`take_stats`].
+use std::fmt::Display;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rustc_hash::FxHashMap;
+
+type MemoFn<'f, K, V> = Rc<dyn Fn(&mut Memo<'f, K, V>, K) -> V + 'f>;
+
+/// Caches the results of a (recursive) function by its argument.
+pub struct Memo<'f, K, V> {
+    cache: FxHashMap<K, V>,
+    func: MemoFn<'f, K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+/// Cache statistics of a [`Memo`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoStats {
+    pub entries: usize,
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl MemoStats {
+    /// Share of lookups that were answered from the cache.
+    pub fn hit_ratio(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} entries, {} hits, {} misses ({:.1}% hit ratio)",
+            self.entries,
+            self.hits,
+            self.misses,
+            self.hit_ratio() * 100.0
+        )
+    }
+}
+
+impl<'f, K: Clone + Eq + Hash, V: Clone> Memo<'f, K, V> {
+    /// Wraps `func`, which receives the memo itself to make (cached) recursive calls.
+    pub fn new(func: impl Fn(&mut Memo<'f, K, V>, K) -> V + 'f) -> Self {
+        Self {
+            cache: FxHashMap::default(),
+            func: Rc::new(func),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the cached result for `key`, computing it on the first call.
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let func = Rc::clone(&self.func);
+        let value = func(self, key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Drops all cached results, e.g. between two independent inputs.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            entries: self.cache.len(),
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+}
+
+/// The combined stats of the memos dropped since the last [`take_stats`], on any thread.
+static DROPPED: [AtomicUsize; 3] = [const { AtomicUsize::new(0) }; 3];
+
+/// The combined stats of all memos dropped since the last call, on any thread (e.g. in rayon
+/// closures), if any was used.
+///
+/// Memos are counted when they are dropped, so one that outlives the part, e.g. in a `static`,
+/// isn't counted. As the stats are shared by the whole process, memos of another part running
+/// at the same time would be counted too.
+pub fn take_stats() -> Option<MemoStats> {
+    let [entries, hits, misses] = DROPPED.each_ref().map(|n| n.swap(0, Ordering::Relaxed));
+    (hits + misses > 0).then_some(MemoStats {
+        entries,
+        hits,
+        misses,
+    })
+}
+
+impl<K, V> Drop for Memo<'_, K, V> {
+    fn drop(&mut self) {
+        let [entries, hits, misses] = &DROPPED;
+        entries.fetch_add(self.cache.len(), Ordering::Relaxed);
+        hits.fetch_add(self.hits, Ordering::Relaxed);
+        misses.fetch_add(self.misses, Ordering::Relaxed);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Held by tests using memos, which all add to the stats of [`take_stats`].
+    static SHARED_STATS: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn caches_recursive_calls() {
+        let _lock = SHARED_STATS.lock().unwrap();
+        let mut fib = Memo::new(|fib, n: u64| {
+            if n < 2 {
+                n
+            } else {
+                fib.get(n - 1) + fib.get(n - 2)
+            }
+        });
+        assert_eq!(fib.get(50), 12_586_269_025);
+
+        let stats = fib.stats();
+        assert_eq!(stats.entries, 51);
+        assert_eq!(stats.misses, 51);
+        assert_eq!(stats.hits, 48);
+
+        fib.get(50);
+        assert_eq!(fib.stats().hits, 49);
+    }
+
+    fn paths(memo: &mut Memo<(usize, usize), u64>, (x, y): (usize, usize)) -> u64 {
+        if x == 0 || y == 0 {
+            return 1;
+        }
+        memo.get((x - 1, y)) + memo.get((x, y - 1))
+    }
+
+    #[test]
+    fn works_with_fn_items_and_captures() {
+        let _lock = SHARED_STATS.lock().unwrap();
+        let mut grid_paths = Memo::new(paths);
+        assert_eq!(grid_paths.get((16, 16)), 601_080_390);
+
+        let blocked = [(1, 1)];
+        let mut avoiding = Memo::new(|memo, (x, y): (i32, i32)| -> u64 {
+            if x < 0 || y < 0 || blocked.contains(&(x, y)) {
+                0
+            } else if x == 0 && y == 0 {
+                1
+            } else {
+                memo.get((x - 1, y)) + memo.get((x, y - 1))
+            }
+        });
+        assert_eq!(avoiding.get((2, 2)), 2);
+
+        avoiding.clear();
+        assert_eq!(avoiding.stats().entries, 0);
+    }
+
+    #[test]
+    fn sums_stats_of_dropped_memos() {
+        let _lock = SHARED_STATS.lock().unwrap();
+        take_stats();
+        let fib = |n| {
+            let mut fib = Memo::new(|fib, n: u64| {
+                if n < 2 {
+                    n
+                } else {
+                    fib.get(n - 1) + fib.get(n - 2)
+                }
+            });
+            fib.get(n);
+        };
+        fib(10);
+        // e.g. a memo in a rayon closure.
+        std::thread::spawn(move || fib(20)).join().unwrap();
+        drop(Memo::new(|_, n: u64| n));
+        let stats = take_stats().unwrap();
+        assert_eq!((stats.entries, stats.misses, stats.hits), (32, 32, 26));
+        assert_eq!(take_stats(), None);
+    }
+
+    #[test]
+    fn hit_ratio() {
+        let stats = MemoStats {
+            entries: 3,
+            hits: 3,
+            misses: 1,
+        };
+        assert_eq!(stats.hit_ratio(), 0.75);
+        assert_eq!(
+            stats.to_string(),
+            "3 entries, 3 hits, 1 misses (75.0% hit ratio)"
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! Matching many patterns at once with an Aho-Corasick automaton.
+//!
+//! Matches may overlap, so `"oneight"` contains both `"one"` and `"eight"`.
+//!
+//! ```
+//! # use advent_of_code::multi_pattern::MultiMatcher;
+//! let digits = MultiMatcher::new([("one", 1), ("eight", 8), ("1", 1), ("8", 8)]);
+//! assert_eq!(digits.first_and_last("xoneightx"), Some((&1, &8)));
+//! ```
+
+/// A pattern occurrence found in a haystack, `start..end` being its byte range.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Match<'a, T> {
+    pub start: usize,
+    pub end: usize,
+    pub value: &'a T,
+}
+
+// derived impls would needlessly require `T: Copy`.
+impl<T> Clone for Match<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Match<'_, T> {}
+
+/// Matches a fixed set of byte patterns, each with an associated value.
+#[derive(Debug, Clone)]
+pub struct MultiMatcher<T> {
+    forward: Automaton,
+    backward: Automaton,
+    patterns: Vec<(usize, T)>,
+    max_len: usize,
+}
+
+impl<T> MultiMatcher<T> {
+    /// Builds a matcher for `patterns`. Empty patterns are ignored.
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = (P, T)>) -> Self {
+        let (bytes, patterns): (Vec<Vec<u8>>, Vec<(usize, T)>) = patterns
+            .into_iter()
+            .map(|(p, value)| (p.as_ref().to_vec(), value))
+            .filter(|(p, _)| !p.is_empty())
+            .map(|(p, value)| {
+                let len = p.len();
+                (p, (len, value))
+            })
+            .unzip();
+        let reversed = bytes
+            .iter()
+            .map(|p| p.iter().rev().copied().collect())
+            .collect::<Vec<Vec<u8>>>();
+
+        Self {
+            forward: Automaton::new(&bytes),
+            backward: Automaton::new(&reversed),
+            max_len: bytes.iter().map(Vec::len).max().unwrap_or(0),
+            patterns,
+        }
+    }
+
+    fn found(&self, id: u32, end: usize) -> Match<'_, T> {
+        let (len, value) = &self.patterns[id as usize];
+        Match {
+            start: end - len,
+            end,
+            value,
+        }
+    }
+
+    /// Yields all (possibly overlapping) matches, ordered by their end position.
+    pub fn find_iter<'a>(&'a self, haystack: &'a str) -> impl Iterator<Item = Match<'a, T>> + 'a {
+        self.forward
+            .run(haystack.as_bytes().iter().copied())
+            .flat_map(move |(i, ids)| ids.iter().map(move |&id| self.found(id, i + 1)))
+    }
+
+    /// Returns the match that starts first; the longest one if several start at the same position.
+    pub fn first<'a>(&'a self, haystack: &'a str) -> Option<Match<'a, T>> {
+        let mut best: Option<Match<T>> = None;
+        for (i, ids) in self.forward.run(haystack.as_bytes().iter().copied()) {
+            // matches ending here or later can't start before `best` anymore.
+            if best.is_some_and(|b| i >= b.start + self.max_len) {
+                break;
+            }
+            for &id in ids {
+                let m = self.found(id, i + 1);
+                if best.is_none_or(|b| (m.start, b.end) < (b.start, m.end)) {
+                    best = Some(m);
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns the match that ends last; the longest one if several end at the same position.
+    pub fn last<'a>(&'a self, haystack: &'a str) -> Option<Match<'a, T>> {
+        let bytes = haystack.as_bytes();
+        let mut best: Option<Match<T>> = None;
+        for (i, ids) in self.backward.run(bytes.iter().rev().copied()) {
+            let start = bytes.len() - 1 - i;
+            if best.is_some_and(|b| start + self.max_len < b.end) {
+                break;
+            }
+            for &id in ids {
+                let (len, value) = &self.patterns[id as usize];
+                let m = Match {
+                    start,
+                    end: start + len,
+                    value,
+                };
+                if best.is_none_or(|b| (m.end, b.start) > (b.end, m.start)) {
+                    best = Some(m);
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns the values of the first and the last match, which may be the same.
+    /// Scans from both ends, stopping as soon as each is found.
+    pub fn first_and_last<'a>(&'a self, haystack: &'a str) -> Option<(&'a T, &'a T)> {
+        let first = self.first(haystack)?;
+        let last = self.last(haystack)?;
+        Some((first.value, last.value))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const ROOT: u32 = 0;
+
+/// A deterministic automaton with a transition for every byte in every state.
+#[derive(Debug, Clone)]
+struct Automaton {
+    next: Vec<[u32; 256]>,
+    /// Ids of the patterns that end in each state, including those reached via suffix links.
+    outputs: Vec<Vec<u32>>,
+}
+
+impl Automaton {
+    fn new(patterns: &[Vec<u8>]) -> Self {
+        // build the trie, `u32::MAX` marking missing edges.
+        let mut next = vec![[u32::MAX; 256]];
+        let mut outputs = vec![Vec::new()];
+        for (id, pattern) in patterns.iter().enumerate() {
+            let mut state = ROOT as usize;
+            for &b in pattern {
+                if next[state][b as usize] == u32::MAX {
+                    next[state][b as usize] = next.len() as u32;
+                    next.push([u32::MAX; 256]);
+                    outputs.push(Vec::new());
+                }
+                state = next[state][b as usize] as usize;
+            }
+            outputs[state].push(id as u32);
+        }
+
+        // fill in missing edges breadth first, following the suffix links.
+        let mut fail = vec![ROOT; next.len()];
+        let mut queue = std::collections::VecDeque::new();
+        for edge in &mut next[ROOT as usize] {
+            match *edge {
+                u32::MAX => *edge = ROOT,
+                child => queue.push_back(child),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let state = state as usize;
+            let inherited = outputs[fail[state] as usize].clone();
+            outputs[state].extend(inherited);
+            let fallbacks = next[fail[state] as usize];
+            for (edge, fallback) in next[state].iter_mut().zip(fallbacks) {
+                match *edge {
+                    u32::MAX => *edge = fallback,
+                    child => {
+                        fail[child as usize] = fallback;
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+
+        Self { next, outputs }
+    }
+
+    /// Feeds `bytes` through the automaton, yielding each position at which patterns end.
+    fn run(&self, bytes: impl Iterator<Item = u8>) -> impl Iterator<Item = (usize, &[u32])> {
+        let mut state = ROOT;
+        bytes.enumerate().filter_map(move |(i, b)| {
+            state = self.next[state as usize][b as usize];
+            let ids = &self.outputs[state as usize];
+            (!ids.is_empty()).then_some((i, ids.as_slice()))
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn digit_patterns() -> Vec<(String, u32)> {
+        let words = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        words
+            .iter()
+            .map(|w| w.to_string())
+            .chain((1..=9).map(|d| d.to_string()))
+            .zip((1..=9).chain(1..=9))
+            .collect()
+    }
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let matcher = MultiMatcher::new(digit_patterns());
+        let found = matcher
+            .find_iter("xtwone3eightwo")
+            .map(|m| (m.start, *m.value))
+            .collect::<Vec<_>>();
+        assert_eq!(found, [(1, 2), (3, 1), (6, 3), (7, 8), (11, 2)]);
+    }
+
+    #[test]
+    fn first_and_last_from_both_ends() {
+        let matcher = MultiMatcher::new(digit_patterns());
+        assert_eq!(matcher.first_and_last("oneight"), Some((&1, &8)));
+        assert_eq!(matcher.first_and_last("abc7def"), Some((&7, &7)));
+        assert_eq!(matcher.first_and_last("nothing"), None);
+        assert_eq!(matcher.first_and_last(""), None);
+
+        let last = matcher.last("4nineeightseven2").unwrap();
+        assert_eq!((last.start, last.end, *last.value), (15, 16, 2));
+    }
+
+    #[test]
+    fn prefers_longest_at_same_position() {
+        let matcher = MultiMatcher::new([("he", 1), ("hers", 2), ("she", 3), ("s", 4)]);
+        let first = matcher.first("ushers").unwrap();
+        assert_eq!((first.start, *first.value), (1, 3));
+        let last = matcher.last("ushers").unwrap();
+        assert_eq!((last.start, *last.value), (2, 2));
+        assert_eq!(matcher.find_iter("ushers").count(), 5);
+    }
+
+    #[test]
+    fn matches_naive_search() {
+        let patterns = digit_patterns();
+        let matcher = MultiMatcher::new(patterns.clone());
+        let haystacks = [
+            "eightwothree",
+            "4nineeightseven2",
+            "zoneight234",
+            "7pqrstsixteen",
+        ];
+        for haystack in haystacks {
+            let mut naive = Vec::new();
+            for start in 0..haystack.len() {
+                for (pattern, value) in &patterns {
+                    if haystack[start..].starts_with(pattern.as_str()) {
+                        naive.push((start, start + pattern.len(), *value));
+                    }
+                }
+            }
+            naive.sort_by_key(|&(start, end, _)| (end, start));
+            let mut found = matcher
+                .find_iter(haystack)
+                .map(|m| (m.start, m.end, *m.value))
+                .collect::<Vec<_>>();
+            found.sort_by_key(|&(start, end, _)| (end, start));
+            assert_eq!(found, naive, "{haystack}");
+
+            let first = naive
+                .iter()
+                .min_by_key(|&&(start, end, _)| (start, usize::MAX - end));
+            let last = naive
+                .iter()
+                .max_by_key(|&&(start, end, _)| (end, usize::MAX - start));
+            assert_eq!(
+                matcher.first_and_last(haystack),
+                first.zip(last).map(|(f, l)| (&f.2, &l.2))
+            );
+        }
+    }
+}
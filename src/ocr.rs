@@ -0,0 +1,190 @@
+//! Recognition of the block letters some puzzles draw as their answer.
+//!
+//! Supports the two fonts used by Advent of Code: letters that are 6 pixels tall
+//! (mostly 4 wide) and letters that are 10 pixels tall (6 wide).
+//! Lit pixels are `#` or `█`, unlit pixels are `.` or spaces.
+use std::error::Error;
+use std::fmt::Display;
+
+use rustc_hash::FxHashMap;
+
+const LETTERS_6: &str = "ABCEFGHIJKLOPRSUYZ";
+const FONT_6: [&str; 6] = [
+    ".##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####",
+    "#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#",
+    "#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.",
+    "####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..",
+    "#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...",
+    "#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####",
+];
+
+const LETTERS_10: &str = "ABCEFGHJKLNPRXZ";
+const FONT_10: [&str; 10] = [
+    "..##...#####...####..######.######..####..#....#....###.#....#.#......#....#.#####..#####..#....#.######",
+    ".#..#..#....#.#....#.#......#......#....#.#....#.....#..#...#..#......##...#.#....#.#....#.#....#......#",
+    "#....#.#....#.#......#......#......#......#....#.....#..#..#...#......##...#.#....#.#....#..#..#.......#",
+    "#....#.#....#.#......#......#......#......#....#.....#..#.#....#......#.#..#.#....#.#....#..#..#......#.",
+    "#....#.#####..#......#####..#####..#......######.....#..##.....#......#.#..#.#####..#####....##......#..",
+    "######.#....#.#......#......#......#..###.#....#.....#..##.....#......#..#.#.#......#..#.....##.....#...",
+    "#....#.#....#.#......#......#......#....#.#....#.....#..#.#....#......#..#.#.#......#...#...#..#...#....",
+    "#....#.#....#.#......#......#......#....#.#....#.#...#..#..#...#......#...##.#......#...#...#..#..#.....",
+    "#....#.#....#.#....#.#......#......#...##.#....#.#...#..#...#..#......#...##.#......#....#.#....#.#.....",
+    "#....#.#####...####..######.#.......###.#.#....#..###...#....#.######.#....#.#......#....#.#....#.######",
+];
+
+/// An error which can be returned when reading block letters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    Empty,
+    UnsupportedHeight(usize),
+    UnknownGlyph { column: usize },
+}
+
+impl Error for OcrError {}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Empty => f.write_str("no lit pixels found"),
+            OcrError::UnsupportedHeight(h) => {
+                write!(f, "letters are {h} pixels tall, expecting 6 or 10")
+            }
+            OcrError::UnknownGlyph { column } => {
+                write!(f, "unknown glyph starting at column {}", column + 1)
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads the letters drawn in `art`, e.g. the multi-line answer of a solution.
+pub fn recognize(art: &str) -> Result<String, OcrError> {
+    let grid = art
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+        .collect::<Vec<Vec<bool>>>();
+    recognize_grid(&grid)
+}
+
+/// Reads the letters drawn in a grid of lit (`true`) and unlit pixels.
+pub fn recognize_grid(grid: &[Vec<bool>]) -> Result<String, OcrError> {
+    let lit_rows = grid
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| row.iter().any(|&p| p))
+        .map(|(y, _)| y)
+        .collect::<Vec<_>>();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Err(OcrError::Empty);
+    };
+    let rows = &grid[top..=bottom];
+
+    let font = match rows.len() {
+        6 => font(&FONT_6, LETTERS_6),
+        10 => font(&FONT_10, LETTERS_10),
+        h => return Err(OcrError::UnsupportedHeight(h)),
+    };
+
+    glyphs(rows)
+        .into_iter()
+        .map(|(column, glyph)| {
+            font.get(&glyph)
+                .copied()
+                .ok_or(OcrError::UnknownGlyph { column })
+        })
+        .collect()
+}
+
+/// Splits rows into glyphs at columns without lit pixels.
+/// Returns the start column of each glyph together with its pixels, row by row.
+fn glyphs(rows: &[Vec<bool>]) -> Vec<(usize, Vec<Vec<bool>>)> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |x: usize| rows.iter().any(|row| row.get(x).copied().unwrap_or(false));
+
+    let mut glyphs = Vec::new();
+    let mut start = None;
+    for x in 0..=width {
+        match (start, x < width && lit(x)) {
+            (None, true) => start = Some(x),
+            (Some(from), false) => {
+                let glyph = rows
+                    .iter()
+                    .map(|row| {
+                        (from..x)
+                            .map(|i| row.get(i).copied().unwrap_or(false))
+                            .collect()
+                    })
+                    .collect();
+                glyphs.push((from, glyph));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    glyphs
+}
+
+fn font(rows: &[&str], letters: &str) -> FxHashMap<Vec<Vec<bool>>, char> {
+    let grid = rows
+        .iter()
+        .map(|row| row.chars().map(|c| c == '#').collect())
+        .collect::<Vec<Vec<bool>>>();
+    glyphs(&grid)
+        .into_iter()
+        .map(|(_, glyph)| glyph)
+        .zip(letters.chars())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_small_letters() {
+        let art = [
+            "#..#.####.###..#....",
+            "#..#.#....#..#.#....",
+            "####.###..#..#.#....",
+            "#..#.#....###..#....",
+            "#..#.#....#.#..#....",
+            "#..#.####.#..#.####.",
+        ]
+        .join("\n");
+        assert_eq!(recognize(&art), Ok("HERL".into()));
+    }
+
+    #[test]
+    fn reads_large_letters_with_padding() {
+        let art = [
+            "",
+            "  ██    #    # ",
+            " █  █   #    # ",
+            "█    █   #  #  ",
+            "█    █   #  #  ",
+            "█    █    ##   ",
+            "██████    ##   ",
+            "█    █   #  #  ",
+            "█    █   #  #  ",
+            "█    █  #    # ",
+            "█    █  #    # ",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(recognize(&art), Ok("AX".into()));
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(recognize("...\n..."), Err(OcrError::Empty));
+        assert_eq!(recognize("#\n#"), Err(OcrError::UnsupportedHeight(2)));
+        let unknown = ["..####", "..####", "..####", "..####", "..####", "..####"].join("\n");
+        assert_eq!(
+            recognize(&unknown),
+            Err(OcrError::UnknownGlyph { column: 2 })
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! Parsing helpers for puzzle inputs: fast ASCII integer extraction, `key: values` splitting
+//! and blank-line separated blocks. Errors carry the (1-based) line they occurred on.
+use std::error::Error;
+use std::fmt::Display;
+
+/// An error which can be returned when parsing puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingSeparator(String),
+    InvalidInt(String),
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ParseErrorKind::MissingSeparator(sep) => {
+                write!(f, "line {}: missing separator {sep:?}", self.line)
+            }
+            ParseErrorKind::InvalidInt(s) => write!(f, "line {}: invalid integer {s:?}", self.line),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Primitive integers that can be parsed from ASCII digits.
+pub trait AsciiInt: Copy {
+    const SIGNED: bool;
+    const ZERO: Self;
+    /// Returns `self * 10 + digit` (or `- digit` when `negative`), [`None`] on overflow.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_ascii_int {
+    ($signed:literal: $($t:ty),*) => {$(
+        impl AsciiInt for $t {
+            const SIGNED: bool = $signed;
+            const ZERO: Self = 0;
+
+            #[inline]
+            fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                let shifted = self.checked_mul(10)?;
+                let digit = digit as Self;
+                if negative {
+                    shifted.checked_sub(digit)
+                } else {
+                    shifted.checked_add(digit)
+                }
+            }
+        }
+    )*};
+}
+
+impl_ascii_int!(false: u8, u16, u32, u64, u128, usize);
+impl_ascii_int!(true: i8, i16, i32, i64, i128, isize);
+
+/// Parses a single integer, e.g. `"42"` or `"-7"`.
+/// A leading `-` is only accepted for signed types. Surrounding whitespace is not allowed.
+#[inline]
+pub fn parse_int<T: AsciiInt>(s: &str) -> Option<T> {
+    let bytes = s.as_bytes();
+    let (negative, digits) = match bytes {
+        [b'-', rest @ ..] if T::SIGNED => (true, rest),
+        [b'+', rest @ ..] => (false, rest),
+        _ => (false, bytes),
+    };
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(T::ZERO, |acc, &b| {
+        if b.is_ascii_digit() {
+            acc.push_digit(b - b'0', negative)
+        } else {
+            None
+        }
+    })
+}
+
+/// Yields every integer in `s`, skipping anything that isn't a digit.
+///
+/// For signed types a `-` directly in front of a number makes it negative, so `"x=-3..5"` yields
+/// `-3` and `5`, and `"1-2"` yields `1` and `-2`. For unsigned types the sign is ignored.
+///
+/// Numbers that don't fit into `T` are skipped, see [`try_ints`] to get an error for them.
+pub fn ints<T: AsciiInt>(s: &str) -> Ints<'_, T> {
+    Ints {
+        bytes: s.as_bytes(),
+        pos: 0,
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// Like [`ints`], but yields an [`ParseErrorKind::InvalidInt`] error for numbers that don't
+/// fit into `T`.
+pub fn try_ints<T: AsciiInt + 'static>(
+    s: &str,
+) -> impl Iterator<Item = Result<T, ParseErrorKind>> + '_ {
+    let mut ints = ints(s);
+    std::iter::from_fn(move || ints.next_int())
+}
+
+/// Iterator returned by [`ints`].
+pub struct Ints<'a, T> {
+    bytes: &'a [u8],
+    pos: usize,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: AsciiInt> Ints<'_, T> {
+    /// The next number, or the text of the next number if it doesn't fit into `T`.
+    #[inline]
+    fn next_int(&mut self) -> Option<Result<T, ParseErrorKind>> {
+        let bytes = self.bytes;
+        let start = self.pos + bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+        let negative = T::SIGNED && start > 0 && bytes[start - 1] == b'-';
+        let end = start
+            + bytes[start..]
+                .iter()
+                .position(|b| !b.is_ascii_digit())
+                .unwrap_or(bytes.len() - start);
+        self.pos = end;
+
+        let value = bytes[start..end]
+            .iter()
+            .try_fold(T::ZERO, |value, &b| value.push_digit(b - b'0', negative));
+        Some(value.ok_or_else(|| {
+            let text = &bytes[start - usize::from(negative)..end];
+            ParseErrorKind::InvalidInt(String::from_utf8_lossy(text).into_owned())
+        }))
+    }
+}
+
+impl<T: AsciiInt> Iterator for Ints<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Ok(value) = self.next_int()? {
+                return Some(value);
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A line of input together with its 1-based line number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self.number,
+            kind,
+        }
+    }
+
+    /// Splits `key: values`-style lines at the first occurrence of `sep`.
+    pub fn split_key(&self, sep: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(sep)
+            .ok_or_else(|| self.error(ParseErrorKind::MissingSeparator(sep.into())))
+    }
+
+    /// Yields every integer on the line, see [`ints`].
+    pub fn ints<T: AsciiInt>(&self) -> Ints<'a, T> {
+        ints(self.text)
+    }
+
+    /// Parses whitespace separated integers, failing on any token that isn't one.
+    pub fn parse_ints<T: AsciiInt>(&self) -> Result<Vec<T>, ParseError> {
+        self.text
+            .split_whitespace()
+            .map(|token| {
+                parse_int(token).ok_or_else(|| self.error(ParseErrorKind::InvalidInt(token.into())))
+            })
+            .collect()
+    }
+}
+
+/// Yields the lines of `input` with their line numbers.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    lines_from(input, 1)
+}
+
+fn lines_from(input: &str, first: usize) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(move |(i, text)| Line {
+        number: first + i,
+        text,
+    })
+}
+
+/// A group of lines that is separated from other groups by blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// 1-based line number of the first line in the block.
+    pub first_line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Block<'a> {
+    /// Yields the lines of the block, numbered relative to the whole input.
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> {
+        lines_from(self.text, self.first_line)
+    }
+}
+
+/// Splits `input` into blocks separated by one or more blank lines.
+pub fn blocks(input: &str) -> impl Iterator<Item = Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        let is_blank = line.trim().is_empty();
+        match (start, is_blank) {
+            (None, false) => start = Some((i + 1, offset)),
+            (Some((first_line, from)), true) => {
+                blocks.push(Block {
+                    first_line,
+                    text: input[from..offset].trim_end(),
+                });
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some((first_line, from)) = start {
+        blocks.push(Block {
+            first_line,
+            text: input[from..].trim_end(),
+        });
+    }
+
+    blocks.into_iter()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_single_ints() {
+        assert_eq!(parse_int::<u32>("1234"), Some(1234));
+        assert_eq!(parse_int::<i64>("-1234"), Some(-1234));
+        assert_eq!(parse_int::<i8>("-128"), Some(i8::MIN));
+        assert_eq!(parse_int::<i8>("128"), None);
+        assert_eq!(parse_int::<u32>("-1"), None);
+        assert_eq!(parse_int::<u32>(""), None);
+        assert_eq!(parse_int::<u32>("12a"), None);
+        assert_eq!(parse_int::<u64>("+7"), Some(7));
+    }
+
+    #[test]
+    fn extracts_all_ints() {
+        let line = "Card  1: 41 -48 | x=-3,y=17";
+        assert_eq!(ints::<i32>(line).collect::<Vec<_>>(), [1, 41, -48, -3, 17]);
+        assert_eq!(ints::<u32>(line).collect::<Vec<_>>(), [1, 41, 48, 3, 17]);
+        assert_eq!(ints::<i32>("1-2").collect::<Vec<_>>(), [1, -2]);
+        assert_eq!(ints::<u8>("no numbers").count(), 0);
+    }
+
+    #[test]
+    fn ints_skip_overflows() {
+        assert_eq!(ints::<u8>("1 256 -3 300").collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(
+            try_ints::<i8>("1 -129 7").collect::<Vec<_>>(),
+            [Ok(1), Err(ParseErrorKind::InvalidInt("-129".into())), Ok(7)]
+        );
+    }
+
+    #[test]
+    fn numbered_lines_and_errors() {
+        let input = "seeds: 79 14\nbad line\nnums: 1 x 3";
+        let lines = lines(input).collect::<Vec<_>>();
+
+        let (key, values) = lines[0].split_key(": ").unwrap();
+        assert_eq!(key, "seeds");
+        assert_eq!(
+            lines[0].parse_ints::<u64>(),
+            Err(lines[0].error(ParseErrorKind::InvalidInt("seeds:".into())))
+        );
+        assert_eq!(ints::<u64>(values).collect::<Vec<_>>(), [79, 14]);
+
+        let err = lines[1].split_key(": ").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.to_string(), "line 2: missing separator \": \"");
+
+        let (_, values) = lines[2].split_key(": ").unwrap();
+        let err = Line {
+            text: values,
+            ..lines[2]
+        }
+        .parse_ints::<u8>()
+        .unwrap_err();
+        assert_eq!(err.to_string(), "line 3: invalid integer \"x\"");
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "seeds: 1 2\n\na-to-b:\n1 2 3\n4 5 6\n\n\nb-to-c:\n7 8 9\n";
+        let blocks = blocks(input).collect::<Vec<_>>();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].text, "seeds: 1 2");
+        assert_eq!(blocks[1].text, "a-to-b:\n1 2 3\n4 5 6");
+        assert_eq!(blocks[2].first_line, 8);
+        let numbers = blocks[1].lines().map(|l| l.number).collect::<Vec<_>>();
+        assert_eq!(numbers, [3, 4, 5]);
+    }
+
+    #[test]
+    fn handles_crlf_blocks() {
+        let blocks = blocks("a\r\nb\r\n\r\nc").collect::<Vec<_>>();
+        assert_eq!(blocks[0].text, "a\r\nb");
+        assert_eq!(blocks[1].text, "c");
+    }
+}
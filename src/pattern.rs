@@ -0,0 +1,512 @@
+//! Declarative line formats, e.g. `Game {id}: {draws:list("; ")}`.
+//!
+//! A [`Pattern`] is a mix of literal text and named captures:
+//!  - `{name}` captures everything up to the next literal (or the end of the line).
+//!  - `{name:list}` does the same, but the value is split on whitespace.
+//!  - `{name:list("sep")}` does the same, but the value is split on `sep`.
+//!  - `{{` and `}}` match literal braces.
+//!
+//! Captured values are converted with [`FromStr`] or matched against nested patterns.
+//! Errors point at the line and column of the offending input.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::parse::{self, Line};
+
+/// An error which can be returned when compiling a [`Pattern`] or matching input against it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    /// 1-based line number, `0` for errors in the pattern itself.
+    pub line: usize,
+    /// 1-based column.
+    pub column: usize,
+    pub kind: PatternErrorKind,
+    /// The line (or pattern) the error occurred in.
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternErrorKind {
+    InvalidPattern(String),
+    ExpectedLiteral(String),
+    TrailingInput,
+    UnknownCapture(String),
+    NotAList(String),
+    InvalidValue(String),
+}
+
+impl Error for PatternError {}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.line == 0 {
+            write!(f, "pattern, column {}: ", self.column)?;
+        } else {
+            write!(f, "line {}, column {}: ", self.line, self.column)?;
+        }
+        match &self.kind {
+            PatternErrorKind::InvalidPattern(msg) => write!(f, "{msg}")?,
+            PatternErrorKind::ExpectedLiteral(lit) => write!(f, "expected {lit:?}")?,
+            PatternErrorKind::TrailingInput => write!(f, "unexpected trailing input")?,
+            PatternErrorKind::UnknownCapture(name) => write!(f, "no capture named `{name}`")?,
+            PatternErrorKind::NotAList(name) => write!(f, "capture `{name}` is not a list")?,
+            PatternErrorKind::InvalidValue(value) => write!(f, "invalid value {value:?}")?,
+        }
+        write!(
+            f,
+            "\n  {}\n  {:>width$}",
+            self.text,
+            "^",
+            width = self.column
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ListSeparator {
+    Whitespace,
+    Str(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Capture {
+        name: String,
+        list: Option<ListSeparator>,
+    },
+}
+
+/// A compiled line format, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl FromStr for Pattern {
+    type Err = PatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl Pattern {
+    /// Compiles a pattern.
+    pub fn new(source: &str) -> Result<Self, PatternError> {
+        let error = |column: usize, msg: &str| PatternError {
+            line: 0,
+            column: column + 1,
+            kind: PatternErrorKind::InvalidPattern(msg.into()),
+            text: source.into(),
+        };
+
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((pos, ch)) = chars.next() {
+            match ch {
+                '{' if chars.next_if(|&(_, c)| c == '{').is_some() => literal.push('{'),
+                '}' if chars.next_if(|&(_, c)| c == '}').is_some() => literal.push('}'),
+                '}' => return Err(error(pos, "unmatched `}`, use `}}` for a literal brace")),
+                '{' => {
+                    let mut inner = String::new();
+                    let mut in_quotes = false;
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) if !in_quotes => break,
+                            Some((_, c)) => {
+                                in_quotes ^= c == '"';
+                                inner.push(c);
+                            }
+                            None => return Err(error(pos, "unclosed capture")),
+                        }
+                    }
+                    if matches!(segments.last(), Some(Segment::Capture { .. }))
+                        && literal.is_empty()
+                    {
+                        return Err(error(pos, "captures need a literal between them"));
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    let capture = Self::parse_capture(&inner).map_err(|msg| error(pos, msg))?;
+                    segments.push(capture);
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self {
+            source: source.into(),
+            segments,
+        })
+    }
+
+    fn parse_capture(inner: &str) -> Result<Segment, &'static str> {
+        let (name, kind) = match inner.split_once(':') {
+            Some((name, kind)) => (name.trim(), Some(kind.trim())),
+            None => (inner.trim(), None),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err("capture names must be non-empty and alphanumeric");
+        }
+
+        let list = match kind {
+            None => None,
+            Some("list") => Some(ListSeparator::Whitespace),
+            Some(kind) => {
+                let sep = kind
+                    .strip_prefix("list(\"")
+                    .and_then(|k| k.strip_suffix("\")"))
+                    .filter(|sep| !sep.is_empty())
+                    .ok_or("expected `list` or `list(\"separator\")`")?;
+                Some(ListSeparator::Str(sep.into()))
+            }
+        };
+
+        Ok(Segment::Capture {
+            name: name.into(),
+            list,
+        })
+    }
+
+    /// Matches a whole line against the pattern.
+    pub fn captures<'a>(&'a self, line: Line<'a>) -> Result<Captures<'a>, PatternError> {
+        self.captures_in(line, 0..line.text.len())
+    }
+
+    fn captures_in<'a>(
+        &'a self,
+        line: Line<'a>,
+        range: Range<usize>,
+    ) -> Result<Captures<'a>, PatternError> {
+        let text = line.text;
+        let mut values = Vec::new();
+        let mut pos = range.start;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Literal(lit) => {
+                    if !text[pos..range.end].starts_with(lit.as_str()) {
+                        return Err(error_at(
+                            line,
+                            pos,
+                            PatternErrorKind::ExpectedLiteral(lit.clone()),
+                        ));
+                    }
+                    pos += lit.len();
+                }
+                Segment::Capture { name, list } => {
+                    let end = match self.segments.get(i + 1) {
+                        Some(Segment::Literal(lit)) => {
+                            let found =
+                                text[pos..range.end].find(lit.as_str()).ok_or_else(|| {
+                                    error_at(
+                                        line,
+                                        range.end,
+                                        PatternErrorKind::ExpectedLiteral(lit.clone()),
+                                    )
+                                })?;
+                            pos + found
+                        }
+                        _ => range.end,
+                    };
+                    values.push(Capture {
+                        name,
+                        list: list.as_ref(),
+                        range: pos..end,
+                    });
+                    pos = end;
+                }
+            }
+        }
+
+        if pos != range.end {
+            return Err(error_at(line, pos, PatternErrorKind::TrailingInput));
+        }
+
+        Ok(Captures { line, values })
+    }
+
+    /// Matches every line of `input` and converts it with [`FromCaptures`].
+    pub fn parse_lines<T: FromCaptures>(&self, input: &str) -> Result<Vec<T>, PatternError> {
+        parse::lines(input)
+            .map(|line| T::from_captures(&self.captures(line)?))
+            .collect()
+    }
+
+    /// The source the pattern was compiled from.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+fn error_at(line: Line<'_>, pos: usize, kind: PatternErrorKind) -> PatternError {
+    PatternError {
+        line: line.number,
+        column: pos + 1,
+        kind,
+        text: line.text.into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone)]
+struct Capture<'a> {
+    name: &'a str,
+    list: Option<&'a ListSeparator>,
+    range: Range<usize>,
+}
+
+/// The values captured from one line, see [`Pattern::captures`].
+#[derive(Debug, Clone)]
+pub struct Captures<'a> {
+    line: Line<'a>,
+    values: Vec<Capture<'a>>,
+}
+
+impl<'a> Captures<'a> {
+    fn find(&self, name: &str) -> Result<&Capture<'a>, PatternError> {
+        self.values
+            .iter()
+            .find(|c| c.name == name)
+            .ok_or_else(|| error_at(self.line, 0, PatternErrorKind::UnknownCapture(name.into())))
+    }
+
+    /// The line the values were captured from.
+    pub fn line(&self) -> Line<'a> {
+        self.line
+    }
+
+    /// The raw captured text.
+    pub fn str(&self, name: &str) -> Result<&'a str, PatternError> {
+        Ok(&self.line.text[self.find(name)?.range.clone()])
+    }
+
+    /// The captured value converted with [`FromStr`], ignoring surrounding whitespace.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, PatternError> {
+        let capture = self.find(name)?;
+        self.convert(trim_range(self.line.text, capture.range.clone()))
+    }
+
+    /// The items of a list capture, converted with [`FromStr`].
+    pub fn list<T: FromStr>(&self, name: &str) -> Result<Vec<T>, PatternError> {
+        self.list_items(name)?
+            .into_iter()
+            .map(|range| self.convert(range))
+            .collect()
+    }
+
+    /// Matches the captured value against another pattern.
+    pub fn sub(&self, name: &str, pattern: &'a Pattern) -> Result<Captures<'a>, PatternError> {
+        let capture = self.find(name)?;
+        pattern.captures_in(self.line, capture.range.clone())
+    }
+
+    /// Matches every item of a list capture against another pattern.
+    pub fn list_with(
+        &self,
+        name: &str,
+        pattern: &'a Pattern,
+    ) -> Result<Vec<Captures<'a>>, PatternError> {
+        self.list_items(name)?
+            .into_iter()
+            .map(|range| pattern.captures_in(self.line, range))
+            .collect()
+    }
+
+    fn convert<T: FromStr>(&self, range: Range<usize>) -> Result<T, PatternError> {
+        let value = &self.line.text[range.clone()];
+        value.parse().map_err(|_| {
+            error_at(
+                self.line,
+                range.start,
+                PatternErrorKind::InvalidValue(value.into()),
+            )
+        })
+    }
+
+    /// Byte ranges of the (trimmed, non-empty) items of a list capture.
+    fn list_items(&self, name: &str) -> Result<Vec<Range<usize>>, PatternError> {
+        let capture = self.find(name)?;
+        let text = self.line.text;
+        let base = capture.range.start;
+        let value = &text[capture.range.clone()];
+
+        let pieces: Vec<(usize, &str)> = match capture.list {
+            None => {
+                return Err(error_at(
+                    self.line,
+                    base,
+                    PatternErrorKind::NotAList(name.into()),
+                ));
+            }
+            Some(ListSeparator::Whitespace) => value
+                .split_whitespace()
+                .map(|s| (s.as_ptr() as usize - value.as_ptr() as usize, s))
+                .collect(),
+            Some(ListSeparator::Str(sep)) => value
+                .split(sep.as_str())
+                .map(|s| (s.as_ptr() as usize - value.as_ptr() as usize, s))
+                .collect(),
+        };
+
+        Ok(pieces
+            .into_iter()
+            .map(|(offset, s)| trim_range(text, base + offset..base + offset + s.len()))
+            .filter(|range| !range.is_empty())
+            .collect())
+    }
+}
+
+fn trim_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let value = &text[range.clone()];
+    let start = range.start + (value.len() - value.trim_start().len());
+    let end = range.end - (value.len() - value.trim_end().len());
+    start..end.max(start)
+}
+
+/// Types that can be built from the captures of a [`Pattern`].
+pub trait FromCaptures: Sized {
+    fn from_captures(captures: &Captures<'_>) -> Result<Self, PatternError>;
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line<'_> {
+        Line { number: 1, text }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Card {
+        id: u32,
+        winning: Vec<u32>,
+        have: Vec<u32>,
+    }
+
+    impl FromCaptures for Card {
+        fn from_captures(c: &Captures<'_>) -> Result<Self, PatternError> {
+            Ok(Card {
+                id: c.get("id")?,
+                winning: c.list("winning")?,
+                have: c.list("have")?,
+            })
+        }
+    }
+
+    #[test]
+    fn parses_typed_structs() {
+        let pattern = Pattern::new("Card {id}: {winning:list} | {have:list}").unwrap();
+        let cards: Vec<Card> = pattern
+            .parse_lines("Card   1: 41 48 | 83  86 6\nCard 2: 13 | 61")
+            .unwrap();
+        assert_eq!(
+            cards[0],
+            Card {
+                id: 1,
+                winning: vec![41, 48],
+                have: vec![83, 86, 6]
+            }
+        );
+        assert_eq!(cards[1].id, 2);
+    }
+
+    #[test]
+    fn nested_patterns() {
+        let game = Pattern::new("Game {id}: {draws:list(\"; \")}").unwrap();
+        let draw = Pattern::new("{cubes:list(\", \")}").unwrap();
+        let cube = Pattern::new("{n} {color}").unwrap();
+
+        let text = "Game 3: 8 green, 6 blue; 20 red";
+        let c = game.captures(line(text)).unwrap();
+        assert_eq!(c.get::<u32>("id").unwrap(), 3);
+        let draws = c.list_with("draws", &draw).unwrap();
+        assert_eq!(draws.len(), 2);
+        let cubes = draws[0].list_with("cubes", &cube).unwrap();
+        assert_eq!(cubes[1].get::<u32>("n").unwrap(), 6);
+        assert_eq!(cubes[1].str("color").unwrap(), "blue");
+
+        // positions in nested errors are relative to the whole line.
+        let strict = Pattern::new("{n}!").unwrap();
+        let err = draws[1].list_with("cubes", &strict).unwrap_err();
+        assert_eq!(err.column, 32);
+    }
+
+    #[test]
+    fn node_table_and_escapes() {
+        let pattern = Pattern::new("{id} = ({left}, {right})").unwrap();
+        let c = pattern.captures(line("AAA = (BBB, CCC)")).unwrap();
+        assert_eq!(c.str("id").unwrap(), "AAA");
+        assert_eq!(c.str("right").unwrap(), "CCC");
+
+        let braces = Pattern::new("{{{x}}}").unwrap();
+        assert_eq!(
+            braces
+                .captures(line("{7}"))
+                .unwrap()
+                .get::<u8>("x")
+                .unwrap(),
+            7
+        );
+    }
+
+    #[derive(Debug)]
+    struct Id(u32);
+
+    impl FromCaptures for Id {
+        fn from_captures(c: &Captures<'_>) -> Result<Self, PatternError> {
+            Ok(Id(c.get("id")?))
+        }
+    }
+
+    #[test]
+    fn reports_line_and_column() {
+        let pattern = Pattern::new("Game {id}: {rest}").unwrap();
+        let ids = pattern.parse_lines::<Id>("Game 7: a").unwrap();
+        assert_eq!(ids[0].0, 7);
+        let err = pattern
+            .parse_lines::<Id>("Game 1: a\nGame 2 b")
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 9));
+        assert_eq!(err.kind, PatternErrorKind::ExpectedLiteral(": ".into()));
+
+        let c = pattern.captures(line("Game x1: 5")).unwrap();
+        let err = c.get::<u32>("id").unwrap_err();
+        assert_eq!(err.column, 6);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 6: invalid value \"x1\"\n  Game x1: 5\n       ^"
+        );
+
+        let err = Pattern::new("abc")
+            .unwrap()
+            .captures(line("abcd"))
+            .unwrap_err();
+        assert_eq!((err.column, err.kind), (4, PatternErrorKind::TrailingInput));
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert!(Pattern::new("{a}{b}").is_err());
+        assert!(Pattern::new("{a").is_err());
+        assert!(Pattern::new("a}").is_err());
+        assert!(Pattern::new("{}").is_err());
+        assert!(Pattern::new("{a:map}").is_err());
+        assert_eq!(Pattern::new("x {a:foo}").unwrap_err().column, 3);
+    }
+}
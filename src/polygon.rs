@@ -0,0 +1,171 @@
+//! Area and enclosed-point helpers for simple polygons on the integer lattice.
+//!
+//! Vertices are `(x, y)` pairs, listed in order along the boundary (either direction).
+//! The polygon is implicitly closed, i.e. the last vertex connects back to the first one.
+
+/// Twice the signed area of the polygon, computed with the shoelace formula.
+/// Positive for counter-clockwise vertices (with y pointing up), negative otherwise.
+/// Doubling keeps the result exact, since lattice polygons can have half-integer areas.
+pub fn signed_double_area(vertices: &[(i64, i64)]) -> i64 {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&(x1, y1), &(x2, y2))| x1 * y2 - x2 * y1)
+        .sum()
+}
+
+/// Area of the polygon (shoelace formula).
+pub fn area(vertices: &[(i64, i64)]) -> f64 {
+    signed_double_area(vertices).abs() as f64 / 2.0
+}
+
+/// Number of lattice points on the boundary of the polygon, vertices included.
+pub fn boundary_points(vertices: &[(i64, i64)]) -> i64 {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&(x1, y1), &(x2, y2))| gcd((x2 - x1).abs(), (y2 - y1).abs()))
+        .sum()
+}
+
+/// Number of lattice points strictly inside the polygon, via Pick's theorem (`A = I + B/2 - 1`).
+pub fn interior_points(vertices: &[(i64, i64)]) -> i64 {
+    (signed_double_area(vertices).abs() - boundary_points(vertices) + 2) / 2
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Even-odd scanline fill over a `width` x `height` grid.
+///
+/// `is_boundary(x, y)` tells whether a cell belongs to the polygon's boundary and
+/// `crosses_north(x, y)` whether that boundary cell connects to the cell above it.
+/// Counting only north-connecting cells handles horizontal runs correctly: a run like `L--7`
+/// crosses the scanline once, while `L--J` doesn't cross it at all.
+///
+/// Returns a mask of the cells that lie strictly inside the boundary.
+pub fn scanline_inside(
+    width: usize,
+    height: usize,
+    is_boundary: impl Fn(usize, usize) -> bool,
+    crosses_north: impl Fn(usize, usize) -> bool,
+) -> Vec<Vec<bool>> {
+    (0..height)
+        .map(|y| {
+            let mut inside = false;
+            (0..width)
+                .map(|x| {
+                    if is_boundary(x, y) {
+                        if crosses_north(x, y) {
+                            inside = !inside;
+                        }
+                        false
+                    } else {
+                        inside
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Counts the cells inside the boundary, see [`scanline_inside`].
+pub fn scanline_count(
+    width: usize,
+    height: usize,
+    is_boundary: impl Fn(usize, usize) -> bool,
+    crosses_north: impl Fn(usize, usize) -> bool,
+) -> usize {
+    scanline_inside(width, height, is_boundary, crosses_north)
+        .iter()
+        .flatten()
+        .filter(|&&inside| inside)
+        .count()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_area_and_points() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(signed_double_area(&square), 32);
+        assert_eq!(area(&square), 16.0);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+    }
+
+    #[test]
+    fn orientation_and_half_areas() {
+        let triangle = [(0, 0), (0, 3), (3, 0)];
+        assert_eq!(signed_double_area(&triangle), -9);
+        assert_eq!(area(&triangle), 4.5);
+        assert_eq!(interior_points(&triangle), 1);
+    }
+
+    #[test]
+    fn unit_step_loop() {
+        // every boundary tile listed, as when walking a pipe loop.
+        let walk = [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+        ];
+        assert_eq!(boundary_points(&walk), 8);
+        assert_eq!(interior_points(&walk), 1);
+    }
+
+    #[test]
+    fn scanline_matches_pick() {
+        let grid: Vec<Vec<char>> = ["F--7.", "|..L7", "|...|", "L---J"]
+            .iter()
+            .map(|l| l.chars().collect())
+            .collect();
+        let mask = scanline_inside(
+            5,
+            4,
+            |x, y| grid[y][x] != '.',
+            |x, y| "|LJ".contains(grid[y][x]),
+        );
+        assert!(mask[1][1] && mask[1][2] && !mask[1][3] && !mask[0][4]);
+        let count = scanline_count(
+            5,
+            4,
+            |x, y| grid[y][x] != '.',
+            |x, y| "|LJ".contains(grid[y][x]),
+        );
+        let walk = [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (3, 1),
+            (4, 1),
+            (4, 2),
+            (4, 3),
+            (3, 3),
+            (2, 3),
+            (1, 3),
+            (0, 3),
+            (0, 2),
+            (0, 1),
+        ];
+        assert_eq!(count, 5);
+        assert_eq!(interior_points(&walk), 5);
+    }
+}
@@ -0,0 +1,552 @@
+//! Property-based testing: random values from seeded [`Generator`]s are checked against a
+//! property, and failing cases are shrunk to a small counterexample before reporting.
+//!
+//! ```
+//! # use advent_of_code::{prop, prop_assert_eq};
+//! prop::check(prop::vec_of(0..100u32, 0..=20), |v| {
+//!     let mut sorted = v.clone();
+//!     sorted.sort();
+//!     prop_assert_eq!(sorted.len(), v.len());
+//!     Ok(())
+//! });
+//! ```
+//!
+//! Runs are deterministic. Set `PROP_SEED` to try other (or replay reported) seeds and
+//! `PROP_CASES` to change the number of cases per property.
+use std::fmt::Debug;
+use std::ops::{Bound, Range, RangeBounds, RangeInclusive};
+use std::panic::{self, AssertUnwindSafe};
+
+/// A small, fast and seedable pseudo-random number generator (SplitMix64).
+/// Not suitable for anything security related.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns an integer in `range`, e.g. `rng.range(1..=6)`.
+    ///
+    /// # Panics
+    /// If the range is empty.
+    pub fn range<T: SampleInt>(&mut self, range: impl RangeBounds<T>) -> T {
+        let lo = match range.start_bound() {
+            Bound::Included(&x) => x.to_i128(),
+            Bound::Excluded(&x) => x.to_i128() + 1,
+            Bound::Unbounded => T::MIN.to_i128(),
+        };
+        let hi = match range.end_bound() {
+            Bound::Included(&x) => x.to_i128(),
+            Bound::Excluded(&x) => x.to_i128() - 1,
+            Bound::Unbounded => T::MAX.to_i128(),
+        };
+        assert!(lo <= hi, "cannot sample from an empty range");
+        let span = (hi - lo) as u128 + 1;
+        let offset = if span > u64::MAX as u128 {
+            self.next_u64() as u128
+        } else {
+            // multiply-shift maps 64 random bits onto the span with negligible bias.
+            (self.next_u64() as u128 * span) >> 64
+        };
+        T::from_i128(lo + offset as i128)
+    }
+
+    /// Returns a float in `0.0..1.0`.
+    pub fn f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns `true` with probability `p`.
+    pub fn bool(&mut self, p: f64) -> bool {
+        self.f64() < p
+    }
+
+    /// Picks an element of `items`.
+    ///
+    /// # Panics
+    /// If `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+/// Primitive integers that [`Rng::range`] can sample.
+pub trait SampleInt: Copy + PartialOrd + Debug {
+    const MIN: Self;
+    const MAX: Self;
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_sample_int {
+    ($($t:ty),*) => {$(
+        impl SampleInt for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            fn from_i128(value: i128) -> Self {
+                value as Self
+            }
+        }
+    )*};
+}
+
+impl_sample_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/* -------------------------------------------------------------------------- */
+
+/// Produces random values of some type.
+pub trait Generator {
+    type Value: Clone + Debug;
+
+    /// Generates a value. `size` grows over a run, starting at 0, and should bound
+    /// how large the value gets (collection lengths, grid dimensions, ...).
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value;
+
+    /// Returns simpler variants of `value`, most aggressive first, to try when it failed a property.
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        Vec::new()
+    }
+}
+
+macro_rules! impl_range_generator {
+    ($($range:ident),*) => {$(
+        /// Uniform integers, shrinking towards the start of the range.
+        impl<T: SampleInt> Generator for $range<T> {
+            type Value = T;
+
+            fn generate(&self, rng: &mut Rng, _size: usize) -> T {
+                rng.range(self.clone())
+            }
+
+            fn shrink(&self, &value: &T) -> Vec<T> {
+                let (start, value) = (self.start().to_i128(), value.to_i128());
+                let mut simpler = vec![start, start + (value - start) / 2, value - 1];
+                simpler.retain(|&v| v >= start && v < value);
+                simpler.dedup();
+                simpler.into_iter().map(T::from_i128).collect()
+            }
+        }
+    )*};
+}
+
+// `Range` has no `start()` method, unlike `RangeInclusive`.
+trait RangeStart<T> {
+    fn start(&self) -> &T;
+}
+
+impl<T> RangeStart<T> for Range<T> {
+    fn start(&self) -> &T {
+        &self.start
+    }
+}
+
+impl_range_generator!(Range, RangeInclusive);
+
+/// See [`from_fn`].
+#[derive(Debug, Clone, Copy)]
+pub struct FromFn<F>(F);
+
+/// A generator defined by a function of the rng and the size. Its values can't be shrunk
+/// structurally, but the runner still looks for failing cases of smaller sizes.
+pub fn from_fn<T, F>(f: F) -> FromFn<F>
+where
+    T: Clone + Debug,
+    F: Fn(&mut Rng, usize) -> T,
+{
+    FromFn(f)
+}
+
+impl<T, F> Generator for FromFn<F>
+where
+    T: Clone + Debug,
+    F: Fn(&mut Rng, usize) -> T,
+{
+    type Value = T;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> T {
+        (self.0)(rng, size)
+    }
+}
+
+/// See [`vec_of`].
+#[derive(Debug, Clone)]
+pub struct VecOf<G> {
+    element: G,
+    len: RangeInclusive<usize>,
+}
+
+/// Vectors of `element`s, with lengths in `len` but at most `size` (unless that's below the minimum).
+pub fn vec_of<G: Generator>(element: G, len: RangeInclusive<usize>) -> VecOf<G> {
+    VecOf { element, len }
+}
+
+impl<G: Generator> Generator for VecOf<G> {
+    type Value = Vec<G::Value>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value {
+        let (min, max) = (*self.len.start(), *self.len.end());
+        let len = rng.range(min..=max.min(size).max(min));
+        (0..len).map(|_| self.element.generate(rng, size)).collect()
+    }
+
+    /// Drops halves, then single elements, then shrinks single elements.
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let min = *self.len.start();
+        let mut simpler = Vec::new();
+        if value.len() / 2 >= min && value.len() > 1 {
+            let half = value.len() / 2;
+            simpler.push(value[..half].to_vec());
+            simpler.push(value[half..].to_vec());
+        }
+        if value.len() > min {
+            for i in 0..value.len() {
+                let mut removed = value.clone();
+                removed.remove(i);
+                simpler.push(removed);
+            }
+        }
+        for (i, element) in value.iter().enumerate() {
+            for smaller in self.element.shrink(element) {
+                let mut replaced = value.clone();
+                replaced[i] = smaller;
+                simpler.push(replaced);
+            }
+        }
+        simpler
+    }
+}
+
+/// See [`select`].
+#[derive(Debug, Clone)]
+pub struct Select<T>(Vec<T>);
+
+/// Picks one of `items`, shrinking towards the first.
+///
+/// # Panics
+/// If `items` is empty.
+pub fn select<T: Clone + Debug + PartialEq>(items: impl IntoIterator<Item = T>) -> Select<T> {
+    let items = items.into_iter().collect::<Vec<_>>();
+    assert!(!items.is_empty(), "cannot select from no items");
+    Select(items)
+}
+
+impl<T: Clone + Debug + PartialEq> Generator for Select<T> {
+    type Value = T;
+
+    fn generate(&self, rng: &mut Rng, _size: usize) -> T {
+        rng.choose(&self.0).clone()
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        let index = self.0.iter().position(|v| v == value).unwrap_or(0);
+        self.0[..index].iter().take(1).cloned().collect()
+    }
+}
+
+macro_rules! impl_tuple_generator {
+    ($($g:ident: $i:tt),*) => {
+        impl<$($g: Generator),*> Generator for ($($g,)*) {
+            type Value = ($($g::Value,)*);
+
+            fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value {
+                ($(self.$i.generate(rng, size),)*)
+            }
+
+            /// Shrinks one component at a time.
+            fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+                let mut simpler = Vec::new();
+                $(
+                    for smaller in self.$i.shrink(&value.$i) {
+                        let mut replaced = value.clone();
+                        replaced.$i = smaller;
+                        simpler.push(replaced);
+                    }
+                )*
+                simpler
+            }
+        }
+    };
+}
+
+impl_tuple_generator!(A: 0, B: 1);
+impl_tuple_generator!(A: 0, B: 1, C: 2);
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of checking a property for one value: `Err` describes why it doesn't hold.
+pub type PropResult = Result<(), String>;
+
+/// Returns an `Err` from the enclosing property unless the condition holds.
+#[macro_export]
+macro_rules! prop_assert {
+    ($cond:expr $(,)?) => {
+        $crate::prop_assert!($cond, "assertion failed: {}", stringify!($cond))
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            return Err(format!($($arg)+));
+        }
+    };
+}
+
+/// Returns an `Err` from the enclosing property unless both sides are equal.
+#[macro_export]
+macro_rules! prop_assert_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    return Err(format!(
+                        "assertion `left == right` failed\n  left: {left:?}\n right: {right:?}"
+                    ));
+                }
+            }
+        }
+    };
+}
+
+/// Checks `property` for values of `generator` with the default [`Runner`].
+///
+/// # Panics
+/// With the failing seed and the smallest failing value found, if the property doesn't hold.
+pub fn check<G: Generator>(generator: G, property: impl Fn(&G::Value) -> PropResult) {
+    Runner::new().run(generator, property);
+}
+
+/// Configures how many cases a property is checked for, and how large they get.
+#[derive(Debug, Clone, Copy)]
+pub struct Runner {
+    cases: usize,
+    max_size: usize,
+    seed: u64,
+    max_shrinks: usize,
+}
+
+/// Failing cases of smaller sizes are searched for with this many seeds per size.
+const SIZE_SHRINK_ATTEMPTS: u64 = 8;
+
+impl Runner {
+    /// 100 cases up to size 100, unless overridden by `PROP_CASES` and `PROP_SEED`.
+    pub fn new() -> Self {
+        let env = |key: &str| std::env::var(key).ok().and_then(|v| v.parse().ok());
+        Self {
+            cases: env("PROP_CASES").unwrap_or(100) as usize,
+            max_size: 100,
+            seed: env("PROP_SEED").unwrap_or(0x2023),
+            max_shrinks: 1000,
+        }
+    }
+
+    pub fn with_cases(self, cases: usize) -> Self {
+        Self { cases, ..self }
+    }
+
+    /// The size of the last case; sizes grow linearly from 0.
+    pub fn with_max_size(self, max_size: usize) -> Self {
+        Self { max_size, ..self }
+    }
+
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Bounds how many shrunk values are tried once a failing case has been found.
+    pub fn with_max_shrinks(self, max_shrinks: usize) -> Self {
+        Self {
+            max_shrinks,
+            ..self
+        }
+    }
+
+    /// Checks `property` for values of `generator`.
+    ///
+    /// # Panics
+    /// With the failing seed and the smallest failing value found, if the property doesn't hold.
+    pub fn run<G: Generator>(&self, generator: G, property: impl Fn(&G::Value) -> PropResult) {
+        let mut seeds = Rng::new(self.seed);
+        for case in 0..self.cases {
+            let case_seed = seeds.next_u64();
+            let size = case * self.max_size / self.cases.max(1);
+            let value = generator.generate(&mut Rng::new(case_seed), size);
+            if let Err(message) = holds(&property, &value) {
+                let (value, message) =
+                    self.shrink(&generator, &property, size, case_seed, value, message);
+                panic!(
+                    "property failed at case {case} of seed {} (size {size}): {message}\n\
+                     minimal failing value: {value:#?}",
+                    self.seed
+                );
+            }
+        }
+    }
+
+    /// Looks for failing values of smaller sizes first, then shrinks structurally.
+    fn shrink<G: Generator>(
+        &self,
+        generator: &G,
+        property: &impl Fn(&G::Value) -> PropResult,
+        size: usize,
+        case_seed: u64,
+        mut value: G::Value,
+        mut message: String,
+    ) -> (G::Value, String) {
+        let mut budget = self.max_shrinks;
+        'sizes: for smaller in 0..size {
+            for attempt in 0..SIZE_SHRINK_ATTEMPTS {
+                if budget == 0 {
+                    break 'sizes;
+                }
+                budget -= 1;
+                let mut rng = Rng::new(case_seed ^ attempt.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+                let candidate = generator.generate(&mut rng, smaller);
+                if let Err(m) = holds(property, &candidate) {
+                    (value, message) = (candidate, m);
+                    break 'sizes;
+                }
+            }
+        }
+
+        'shrink: while budget > 0 {
+            for candidate in generator.shrink(&value) {
+                if budget == 0 {
+                    break 'shrink;
+                }
+                budget -= 1;
+                if let Err(m) = holds(property, &candidate) {
+                    (value, message) = (candidate, m);
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        (value, message)
+    }
+}
+
+impl Default for Runner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs the property, turning panics into failures.
+fn holds<T>(property: &impl Fn(&T) -> PropResult, value: &T) -> PropResult {
+    match panic::catch_unwind(AssertUnwindSafe(|| property(value))) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Err(format!("panicked: {message}"))
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_is_deterministic_and_in_range() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..1000 {
+            let x = a.range(-3..=3i32);
+            assert_eq!(x, b.range(-3..=3i32));
+            assert!((-3..=3).contains(&x));
+            assert!(a.range(10..11u8) == 10 && b.range(10..11u8) == 10);
+        }
+        assert!((0..1000)
+            .map(|_| a.range(..))
+            .any(|x: u64| x > u32::MAX as u64));
+
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            counts[a.range(0..6usize)] += 1;
+        }
+        assert!(
+            counts.iter().all(|&n| (800..1200).contains(&n)),
+            "{counts:?}"
+        );
+
+        let mut items = (0..20).collect::<Vec<_>>();
+        a.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn passing_property() {
+        Runner::new()
+            .with_cases(50)
+            .run((0..10u32, 0..10u32), |&(a, b)| {
+                prop_assert_eq!(a + b, b + a);
+                prop_assert!(a < 10 && b < 10);
+                Ok(())
+            });
+    }
+
+    #[test]
+    fn sizes_bound_vectors() {
+        let generator = vec_of(0..5u8, 1..=1000);
+        let mut rng = Rng::new(1);
+        for size in 0..20 {
+            let len = generator.generate(&mut rng, size).len();
+            assert!((1..=size.max(1)).contains(&len));
+        }
+    }
+
+    fn failure(run: impl FnOnce() + panic::UnwindSafe) -> String {
+        let payload = panic::catch_unwind(run).unwrap_err();
+        payload.downcast_ref::<String>().unwrap().clone()
+    }
+
+    #[test]
+    fn shrinks_failing_vectors() {
+        let message = failure(|| {
+            check(vec_of(0..1000u32, 0..=50), |v| {
+                prop_assert!(v.iter().all(|&x| x < 500), "too large: {v:?}");
+                Ok(())
+            })
+        });
+        assert!(message.contains("too large: [500]"), "{message}");
+    }
+
+    #[test]
+    fn reports_panics() {
+        let message = failure(|| {
+            check(from_fn(|rng, size| rng.range(0..=size)), |&n| {
+                assert!(n < 30, "n is {n}");
+                Ok(())
+            })
+        });
+        assert!(message.contains("panicked: n is "), "{message}");
+    }
+}
@@ -0,0 +1,215 @@
+//! Extrapolation of integer sequences that follow a polynomial rule.
+//!
+//! All arithmetic is checked: instead of silently wrapping, an overflow is reported as
+//! [`SequenceError::Overflow`].
+use std::error::Error;
+use std::fmt::Display;
+
+/// An error which can be returned when extrapolating a sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceError {
+    Empty,
+    Overflow,
+}
+
+impl Error for SequenceError {}
+
+impl Display for SequenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SequenceError::Empty => f.write_str("cannot extrapolate an empty sequence"),
+            SequenceError::Overflow => f.write_str("integer overflow while extrapolating"),
+        }
+    }
+}
+
+/// Which way to extrapolate: past the last value or before the first one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Backward,
+}
+
+/// How to compute extrapolated values. Both methods yield identical results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Extend the table of finite differences.
+    FiniteDifferences,
+    /// Evaluate the interpolating polynomial with exact rational arithmetic.
+    Lagrange,
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the next value of the sequence.
+pub fn next_value(values: &[i64]) -> Result<i64, SequenceError> {
+    extrapolate(values, Direction::Forward, 1, Method::FiniteDifferences)
+}
+
+/// Returns the value that precedes the sequence.
+pub fn previous_value(values: &[i64]) -> Result<i64, SequenceError> {
+    extrapolate(values, Direction::Backward, 1, Method::FiniteDifferences)
+}
+
+/// Returns the value `steps` positions past the end (or before the start) of the sequence.
+/// `steps == 0` returns the last (or first) value itself.
+pub fn extrapolate(
+    values: &[i64],
+    direction: Direction,
+    steps: usize,
+    method: Method,
+) -> Result<i64, SequenceError> {
+    let reversed;
+    let values = match direction {
+        Direction::Forward => values,
+        Direction::Backward => {
+            // a polynomial read backwards is still a polynomial of the same degree.
+            reversed = values.iter().rev().copied().collect::<Vec<_>>();
+            &reversed
+        }
+    };
+
+    match method {
+        Method::FiniteDifferences => extrapolate_differences(values, steps),
+        Method::Lagrange => extrapolate_lagrange(values, steps),
+    }
+}
+
+/// Returns the degree of the lowest-degree polynomial that generates the sequence,
+/// i.e. the number of difference rows needed until a row is constant.
+/// A sequence with `n` values always fits a polynomial of degree `n - 1`.
+pub fn degree(values: &[i64]) -> Result<usize, SequenceError> {
+    Ok(difference_table(values)?.len() - 1)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Builds rows of differences until one of them is constant.
+fn difference_table(values: &[i64]) -> Result<Vec<Vec<i64>>, SequenceError> {
+    if values.is_empty() {
+        return Err(SequenceError::Empty);
+    }
+
+    let mut rows = vec![values.to_vec()];
+    while let Some(row) = rows.last() {
+        if row.iter().all(|&n| n == row[0]) {
+            break;
+        }
+        let next = row
+            .windows(2)
+            .map(|w| w[1].checked_sub(w[0]).ok_or(SequenceError::Overflow))
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.push(next);
+    }
+    Ok(rows)
+}
+
+fn extrapolate_differences(values: &[i64], steps: usize) -> Result<i64, SequenceError> {
+    let mut lasts = difference_table(values)?
+        .iter()
+        .map(|row| row[row.len() - 1])
+        .collect::<Vec<_>>();
+
+    for _ in 0..steps {
+        for k in (0..lasts.len() - 1).rev() {
+            lasts[k] = lasts[k]
+                .checked_add(lasts[k + 1])
+                .ok_or(SequenceError::Overflow)?;
+        }
+    }
+    Ok(lasts[0])
+}
+
+fn extrapolate_lagrange(values: &[i64], steps: usize) -> Result<i64, SequenceError> {
+    // only `degree + 1` points are needed, which keeps the intermediate products small.
+    let points = &values[values.len() - (degree(values)? + 1)..];
+    let target = i128::try_from(points.len() - 1 + steps).map_err(|_| SequenceError::Overflow)?;
+    lagrange_at(points, target)
+}
+
+/// Evaluates the polynomial through `(i, points[i])` at `x`.
+///
+/// Every basis polynomial `L_i(x) = prod_{j != i} (x - j) / (i - j)` shares the denominator
+/// `(n - 1)!` up to sign and a binomial factor, so the sum is computed exactly over that
+/// common denominator and divided once at the end.
+fn lagrange_at(points: &[i64], x: i128) -> Result<i64, SequenceError> {
+    let n = points.len();
+    let overflow = || SequenceError::Overflow;
+
+    let mut numerator: i128 = 0;
+    let mut binomial: i128 = 1; // C(n - 1, i)
+    for (i, &y) in points.iter().enumerate() {
+        let mut term = i128::from(y).checked_mul(binomial).ok_or_else(overflow)?;
+        for j in (0..n).filter(|&j| j != i) {
+            term = term.checked_mul(x - j as i128).ok_or_else(overflow)?;
+        }
+        if (n - 1 - i) % 2 == 1 {
+            term = -term;
+        }
+        numerator = numerator.checked_add(term).ok_or_else(overflow)?;
+        binomial = binomial * (n - 1 - i) as i128 / (i + 1) as i128;
+    }
+
+    let denominator = (1..n as i128)
+        .try_fold(1i128, |acc, k| acc.checked_mul(k))
+        .ok_or_else(overflow)?;
+    debug_assert_eq!(numerator % denominator, 0, "integer points, integer values");
+    i64::try_from(numerator / denominator).map_err(|_| overflow())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    const METHODS: [Method; 2] = [Method::FiniteDifferences, Method::Lagrange];
+
+    #[test]
+    fn next_and_previous() {
+        assert_eq!(next_value(&[0, 3, 6, 9, 12, 15]), Ok(18));
+        assert_eq!(next_value(&[10, 13, 16, 21, 30, 45]), Ok(68));
+        assert_eq!(previous_value(&[10, 13, 16, 21, 30, 45]), Ok(5));
+        assert_eq!(previous_value(&[1, 3, 6, 10, 15, 21]), Ok(0));
+    }
+
+    #[test]
+    fn methods_agree_over_many_steps() {
+        // n^3 - 2n^2 + 7
+        let f = |n: i64| n.pow(3) - 2 * n.pow(2) + 7;
+        let values = (0..8).map(f).collect::<Vec<_>>();
+        for method in METHODS {
+            for steps in 0..20 {
+                let fwd = extrapolate(&values, Direction::Forward, steps, method);
+                let bwd = extrapolate(&values, Direction::Backward, steps, method);
+                assert_eq!(fwd, Ok(f(7 + steps as i64)), "{method:?}");
+                assert_eq!(bwd, Ok(f(-(steps as i64))), "{method:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn detects_degree() {
+        assert_eq!(degree(&[5, 5, 5]), Ok(0));
+        assert_eq!(degree(&[1, 3, 6, 10, 15, 21]), Ok(2));
+        assert_eq!(degree(&[1, 2, 4, 8]), Ok(3));
+        assert_eq!(degree(&[42]), Ok(0));
+        assert_eq!(degree(&[]), Err(SequenceError::Empty));
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let values = [i64::MAX - 2, i64::MAX - 1, i64::MAX];
+        for method in METHODS {
+            assert_eq!(
+                extrapolate(&values, Direction::Forward, 1, method),
+                Err(SequenceError::Overflow)
+            );
+            assert_eq!(
+                extrapolate(&values, Direction::Backward, 1, method),
+                Ok(i64::MAX - 3)
+            );
+        }
+        assert_eq!(degree(&[i64::MIN, i64::MAX]), Err(SequenceError::Overflow));
+    }
+}
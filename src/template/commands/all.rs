@@ -1,6 +1,7 @@
 use std::io;
 
 use crate::template::{
+    metadata,
     readme_benchmarks::{self, Timings},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
@@ -14,7 +15,11 @@ pub fn handle(is_release: bool, is_timed: bool) {
             println!();
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        let title = metadata::title(day);
+        match &title {
+            Some(title) => println!("{ANSI_BOLD}Day {day}: {title}{ANSI_RESET}"),
+            None => println!("{ANSI_BOLD}Day {day}{ANSI_RESET}"),
+        }
         println!("------");
 
         let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
@@ -22,7 +27,8 @@ pub fn handle(is_release: bool, is_timed: bool) {
         if output.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            let mut val = child_commands::parse_exec_time(&output, day);
+            val.title = title;
             timings.push(val);
         }
     });
@@ -128,6 +134,7 @@ mod child_commands {
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
         let mut timings = super::Timings {
             day,
+            title: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
@@ -171,7 +178,7 @@ mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
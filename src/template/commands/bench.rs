@@ -0,0 +1,26 @@
+use std::process;
+
+use crate::template::commands::solve;
+use crate::Day;
+
+/// Benches an optimized build of the solution of `day`. With `compare_variants`, every variant
+/// of each part is benched instead, checking that they all give the same answer.
+pub fn handle(day: Day, compare_variants: bool) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--release".to_string(),
+        "--".to_string(),
+    ];
+
+    match compare_variants {
+        true => cmd_args.push("--compare-variants".to_string()),
+        false => cmd_args.push("--time".to_string()),
+    }
+
+    if !solve::run(&cmd_args).success() {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,59 @@
+use std::{fs, process};
+
+use crate::template::crypt::{self, Key};
+use crate::{all_days, Day};
+
+/// Restores the plain input and puzzle description of `day`, or of every day if it's
+/// [`None`], from their encrypted copies. Existing files that differ are only overwritten
+/// if `force` is set.
+pub fn handle(day: Option<Day>, force: bool) {
+    let key = Key::load().unwrap_or_else(|e| {
+        eprintln!("Failed to load key: {e}");
+        process::exit(1);
+    });
+
+    let (mut found, mut skipped) = (false, false);
+    for path in day
+        .map_or_else(|| all_days().collect(), |day| vec![day])
+        .into_iter()
+        .flat_map(crypt::data_files)
+    {
+        let encrypted_path = crypt::encrypted_path(&path);
+        let Ok(data) = fs::read(&encrypted_path) else {
+            continue;
+        };
+        found = true;
+        let plaintext = crypt::decrypt(&key, &data, &encrypted_path).unwrap_or_else(|e| {
+            eprintln!("Failed to decrypt: {e}");
+            process::exit(1);
+        });
+
+        match fs::read(&path) {
+            Ok(existing) if existing == plaintext => continue,
+            Ok(_) if !force => {
+                eprintln!(
+                    "Skipped \"{}\": it differs from its encrypted copy. Pass --force to overwrite it.",
+                    path.display()
+                );
+                skipped = true;
+                continue;
+            }
+            _ => {}
+        }
+        match fs::write(&path, plaintext) {
+            Ok(()) => println!("Decrypted \"{}\"", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    if !found {
+        eprintln!("There are no encrypted inputs or puzzles to decrypt.");
+        process::exit(1);
+    }
+    if skipped {
+        process::exit(1);
+    }
+}
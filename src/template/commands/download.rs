@@ -1,4 +1,4 @@
-use crate::template::aoc_cli;
+use crate::template::{aoc_cli, metadata};
 use crate::Day;
 use std::process;
 
@@ -12,4 +12,10 @@ pub fn handle(day: Day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
+
+    match metadata::update_from_puzzle(day) {
+        Ok(Some(title)) => println!("🎄 Saved title \"{title}\" to \"data/metadata/{day}.txt\"."),
+        Ok(None) => eprintln!("Could not find the puzzle title in \"data/puzzles/{day}.md\"."),
+        Err(e) => eprintln!("Failed to save the puzzle title: {e}"),
+    }
 }
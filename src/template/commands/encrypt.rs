@@ -0,0 +1,77 @@
+use std::{fs, process};
+
+use crate::template::crypt::{self, Key};
+use crate::template::input;
+use crate::{all_days, Day};
+
+/// Encrypts the input and puzzle description of `day`, or of every day if it's [`None`],
+/// next to the plain files. Inputs that fail [`input::check`] are skipped, and encrypted
+/// copies with different contents are only overwritten if `force` is set.
+pub fn handle(day: Option<Day>, force: bool) {
+    let key = Key::load().unwrap_or_else(|e| {
+        eprintln!("Failed to load key: {e}");
+        process::exit(1);
+    });
+
+    let (mut found, mut skipped) = (false, false);
+    for (path, is_input) in day
+        .map_or_else(|| all_days().collect(), |day| vec![day])
+        .into_iter()
+        .flat_map(|day| {
+            let [input, puzzle] = crypt::data_files(day);
+            [(input, true), (puzzle, false)]
+        })
+    {
+        let Ok(plaintext) = fs::read(&path) else {
+            continue;
+        };
+        found = true;
+        if is_input {
+            if let Err(e) = input::check(&String::from_utf8_lossy(&plaintext), &path) {
+                eprintln!("Skipped: {e}");
+                skipped = true;
+                continue;
+            }
+        }
+
+        let encrypted_path = crypt::encrypted_path(&path);
+        match fs::read(&encrypted_path) {
+            // encryption is randomised, so unchanged files are kept to keep diffs quiet.
+            Ok(data)
+                if crypt::decrypt(&key, &data, &encrypted_path)
+                    .is_ok_and(|existing| existing == plaintext) =>
+            {
+                println!("Kept \"{}\", it is unchanged", encrypted_path.display());
+                continue;
+            }
+            Ok(_) if !force => {
+                eprintln!(
+                    "Skipped \"{}\": its encrypted copy differs. Pass --force to overwrite it.",
+                    path.display()
+                );
+                skipped = true;
+                continue;
+            }
+            _ => {}
+        }
+        match fs::write(&encrypted_path, crypt::encrypt(&key, &plaintext)) {
+            Ok(()) => println!(
+                "Encrypted \"{}\" to \"{}\"",
+                path.display(),
+                encrypted_path.display()
+            ),
+            Err(e) => {
+                eprintln!("Failed to write \"{}\": {e}", encrypted_path.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    if !found {
+        eprintln!("There are no inputs or puzzles to encrypt.");
+        process::exit(1);
+    }
+    if skipped {
+        process::exit(1);
+    }
+}
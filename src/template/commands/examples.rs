@@ -0,0 +1,328 @@
+use std::{
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    path::Path,
+    process,
+    str::FromStr,
+};
+
+use crate::template::puzzle::{self, PartDescription};
+use crate::template::{examples, input, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+/// A code block picked on the command line: `<n>` for the n-th block of part one, or
+/// `<part>:<n>` for the n-th block of another part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pick {
+    pub part: u8,
+    pub block: usize,
+}
+
+impl FromStr for Pick {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (part, block) = s.split_once(':').unwrap_or(("1", s));
+        match (part.trim().parse(), block.trim().parse()) {
+            (Ok(part @ 1..=2), Ok(block)) => Ok(Pick { part, block }),
+            _ => Err(format!(
+                "invalid pick \"{s}\", expected a block number like 2, or a part and block like 2:3"
+            )),
+        }
+    }
+}
+
+/// Writes the examples and answers found in the puzzle description of `day`. A part with a
+/// [`Pick`] uses that code block; the others use their likely block, asking when it's a guess.
+pub fn handle_extract(day: Day, picks: &[Pick], force: bool) {
+    let puzzle_path = format!("data/puzzles/{day}.md");
+    let text = input::read(Path::new(&puzzle_path), false, false).unwrap_or_else(|e| {
+        eprintln!("{e}\nTry running \"cargo download {day}\".");
+        process::exit(1);
+    });
+
+    let parts = puzzle::parse(&text);
+    let Some(chosen) = select_examples(&parts, picks, choose_block) else {
+        eprintln!("There is no example in \"{puzzle_path}\".");
+        process::exit(1);
+    };
+    let files = example_files(day, &chosen);
+
+    let mut skipped = false;
+    // a part that used to have its own example would still be run and checked.
+    for part in 2..=chosen.len() {
+        let example_path = format!("data/examples/{day}-{part}.txt");
+        if !Path::new(&example_path).exists() || files.iter().any(|f| f.path == example_path) {
+            continue;
+        }
+        if !force {
+            eprintln!(
+                "Skipped removing \"{example_path}\": part {part} now uses another example. Pass --force to remove it."
+            );
+            skipped = true;
+            continue;
+        }
+        let answers_path = examples::answers_path(Path::new(&example_path));
+        let removed = fs::remove_file(&example_path).and_then(|()| match answers_path.exists() {
+            true => fs::remove_file(&answers_path),
+            false => Ok(()),
+        });
+        match removed {
+            Ok(()) => println!("Removed example file \"{example_path}\""),
+            Err(e) => {
+                eprintln!("Failed to remove example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    for ExampleFile {
+        path,
+        block,
+        answers,
+    } in files
+    {
+        let answers_path = examples::answers_path(Path::new(&path));
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        if !force && !existing.trim().is_empty() && existing != block {
+            eprintln!("Skipped \"{path}\": it already exists. Pass --force to overwrite it.");
+            skipped = true;
+            continue;
+        }
+
+        // answers recorded earlier would be checked against the new example.
+        let written = fs::write(&path, block).and_then(|()| match answers.is_empty() {
+            true if answers_path.exists() => fs::remove_file(&answers_path),
+            true => Ok(()),
+            false => fs::write(&answers_path, &answers),
+        });
+        match written {
+            Ok(()) => {
+                println!("Created example file \"{path}\"");
+                for line in answers.lines() {
+                    println!("  {line}");
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if skipped {
+        process::exit(1);
+    }
+}
+
+/// The example and answer of each part. Parts get their block from `choose`, see
+/// [`choose_block`], and the last [`Pick`] for them. Returns [`None`] if part one has no example.
+fn select_examples(
+    parts: &[PartDescription],
+    picks: &[Pick],
+    mut choose: impl FnMut(&PartDescription, u8, Option<usize>) -> Option<String>,
+) -> Option<Vec<(String, Option<String>)>> {
+    let mut chosen: Vec<(String, Option<String>)> = vec![];
+    for (i, part) in parts.iter().enumerate() {
+        let number = i as u8 + 1;
+        let pick = picks
+            .iter()
+            .rev()
+            .find(|pick| pick.part == number)
+            .map(|pick| pick.block);
+        let block = match choose(part, number, pick) {
+            Some(block) => block,
+            // part two often reuses the example of part one.
+            None if number == 2 => match chosen.first() {
+                Some((block, _)) => block.clone(),
+                None => continue,
+            },
+            None => return None,
+        };
+        let answer = part.answer.as_ref().map(|answer| answer.1.clone());
+        chosen.push((input::normalize(&block, false), answer));
+    }
+    Some(chosen)
+}
+
+/// An example file to write, with the answers of the parts using it.
+#[derive(Debug, PartialEq, Eq)]
+struct ExampleFile {
+    path: String,
+    block: String,
+    answers: String,
+}
+
+/// One file per distinct example: `NN.txt` for the first one, and `NN-<part>.txt` for a part
+/// with another example.
+fn example_files(day: Day, chosen: &[(String, Option<String>)]) -> Vec<ExampleFile> {
+    let mut files: Vec<(&String, Vec<u8>)> = vec![];
+    for (part, (block, _)) in (1..).zip(chosen) {
+        match files.iter_mut().find(|(existing, _)| *existing == block) {
+            Some((_, parts)) => parts.push(part),
+            None => files.push((block, vec![part])),
+        }
+    }
+
+    files
+        .into_iter()
+        .enumerate()
+        .map(|(i, (block, parts))| ExampleFile {
+            path: match i {
+                0 => format!("data/examples/{day}.txt"),
+                _ => format!("data/examples/{day}-{}.txt", parts[0]),
+            },
+            block: block.clone(),
+            answers: parts
+                .iter()
+                .filter_map(|&part| {
+                    let answer = chosen[usize::from(part - 1)].1.as_ref()?;
+                    Some(format!("Part {part}: {answer}\n"))
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// The example of a part: the block its answer likely belongs to, the one picked on the
+/// command line, or the one the user chooses if there is a terminal to ask in.
+fn choose_block(part: &PartDescription, number: u8, pick: Option<usize>) -> Option<String> {
+    let likely = part.likely_block()?;
+    let index = match pick {
+        Some(pick) if (1..=part.blocks.len()).contains(&pick) => pick - 1,
+        Some(pick) => {
+            eprintln!(
+                "Part {number} has {} code blocks, there is no block {pick}.",
+                part.blocks.len()
+            );
+            process::exit(1);
+        }
+        None if part.is_ambiguous() && io::stdin().is_terminal() => ask(part, number, likely),
+        None if part.is_ambiguous() => {
+            println!(
+                "Guessed code block {} of part {number} as its example, pass --pick to choose another.",
+                likely + 1
+            );
+            likely
+        }
+        None => likely,
+    };
+    Some(part.blocks[index].1.clone())
+}
+
+fn ask(part: &PartDescription, number: u8, likely: usize) -> usize {
+    println!("{ANSI_BOLD}Part {number}{ANSI_RESET} has several code blocks:");
+    for (i, (_, block)) in part.blocks.iter().enumerate() {
+        let lines = block.lines().count();
+        let first = block.lines().next().unwrap_or_default();
+        println!(
+            "  [{}] {first} {ANSI_ITALIC}({lines} lines){ANSI_RESET}",
+            i + 1
+        );
+    }
+
+    loop {
+        print!("Which one is the example? [{}] ", likely + 1);
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line).unwrap_or(0) == 0 {
+            return likely;
+        }
+        match line.trim() {
+            "" => return likely,
+            choice => match choice.parse::<usize>() {
+                Ok(choice) if (1..=part.blocks.len()).contains(&choice) => return choice - 1,
+                _ => println!("Enter a number from 1 to {}.", part.blocks.len()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_picks() {
+        assert_eq!("2".parse(), Ok(Pick { part: 1, block: 2 }));
+        assert_eq!("2:3".parse(), Ok(Pick { part: 2, block: 3 }));
+        assert!("3:1".parse::<Pick>().is_err());
+        assert!("a".parse::<Pick>().is_err());
+    }
+
+    fn part(blocks: &[&str], answer: Option<&str>) -> PartDescription {
+        PartDescription {
+            blocks: (0..)
+                .step_by(10)
+                .zip(blocks.iter().map(|b| b.to_string()))
+                .collect(),
+            answer: answer.map(|answer| (blocks.len() * 10, answer.to_string())),
+        }
+    }
+
+    /// Picks the picked block or the likely one, where `choose_block` would ask.
+    fn choose(part: &PartDescription, _: u8, pick: Option<usize>) -> Option<String> {
+        let index = pick.map_or(part.likely_block()?, |pick| pick - 1);
+        Some(part.blocks[index].1.clone())
+    }
+
+    #[test]
+    fn selects_examples() {
+        let parts = [part(&["a\n", "b\n"], Some("1")), part(&[], Some("2"))];
+        let chosen = select_examples(&parts, &[], choose).unwrap();
+        assert_eq!(
+            chosen,
+            [
+                ("b\n".to_string(), Some("1".to_string())),
+                ("b\n".to_string(), Some("2".to_string()))
+            ]
+        );
+
+        let parts = [part(&["a\n", "b\n"], None), part(&["c\r\n\n"], None)];
+        let picks = [Pick { part: 1, block: 2 }, Pick { part: 1, block: 1 }];
+        let chosen = select_examples(&parts, &picks, choose).unwrap();
+        assert_eq!(
+            chosen,
+            [("a\n".to_string(), None), ("c\n".to_string(), None)]
+        );
+
+        assert_eq!(select_examples(&[part(&[], Some("1"))], &[], choose), None);
+    }
+
+    #[test]
+    fn groups_parts_by_example() {
+        let day = crate::day!(3);
+        let shared = [
+            ("a\n".to_string(), Some("1".to_string())),
+            ("a\n".to_string(), Some("2".to_string())),
+        ];
+        assert_eq!(
+            example_files(day, &shared),
+            [ExampleFile {
+                path: "data/examples/03.txt".to_string(),
+                block: "a\n".to_string(),
+                answers: "Part 1: 1\nPart 2: 2\n".to_string(),
+            }]
+        );
+
+        let separate = [
+            ("a\n".to_string(), None),
+            ("b\n".to_string(), Some("2".to_string())),
+        ];
+        assert_eq!(
+            example_files(day, &separate),
+            [
+                ExampleFile {
+                    path: "data/examples/03.txt".to_string(),
+                    block: "a\n".to_string(),
+                    answers: String::new(),
+                },
+                ExampleFile {
+                    path: "data/examples/03-2.txt".to_string(),
+                    block: "b\n".to_string(),
+                    answers: "Part 2: 2\n".to_string(),
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::{fs, path::Path, process};
+
+use crate::generate;
+use crate::template::crypt;
+use crate::{all_days, Day};
+
+/// Used when no seed is given, so that generated inputs are reproducible.
+const DEFAULT_SEED: u64 = 2023;
+
+/// Writes generated inputs for `day`, or every day with a generator if it's [`None`].
+/// Existing, non-empty inputs and days with an encrypted input are only overwritten if `force`
+/// is set.
+pub fn handle(day: Option<Day>, seed: Option<u64>, force: bool) {
+    let seed = seed.unwrap_or(DEFAULT_SEED);
+    let days = match day {
+        Some(day) if !generate::has_generator(day) => {
+            eprintln!("There is no input generator for day {day}.");
+            process::exit(1);
+        }
+        Some(day) => vec![day],
+        None => all_days()
+            .filter(|&day| generate::has_generator(day))
+            .collect(),
+    };
+
+    if let Err(e) = fs::create_dir_all("data/inputs") {
+        eprintln!("Failed to create input directory: {e}");
+        process::exit(1);
+    }
+
+    let mut skipped = false;
+    for day in days {
+        let input_path = format!("data/inputs/{day}.txt");
+        let path = Path::new(&input_path);
+        if !force && is_non_empty(path) {
+            eprintln!("Skipped \"{input_path}\": it already exists. Pass --force to overwrite it.");
+            skipped = true;
+            continue;
+        }
+        // a generated input would shadow the encrypted copy of the real one.
+        let encrypted = crypt::encrypted_path(path);
+        if !force && encrypted.exists() {
+            eprintln!(
+                "Skipped \"{input_path}\": \"{}\" exists. Pass --force to generate it anyway.",
+                encrypted.display()
+            );
+            skipped = true;
+            continue;
+        }
+
+        let input = generate::full_input(day, seed).expect("day should have a generator");
+        match fs::write(&input_path, input + "\n") {
+            Ok(()) => println!("Generated input file \"{input_path}\" (seed {seed})"),
+            Err(e) => {
+                eprintln!("Failed to write input file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if skipped && day.is_some() {
+        process::exit(1);
+    }
+}
+
+fn is_non_empty(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|meta| meta.len() > 0)
+}
@@ -0,0 +1,25 @@
+use std::{fs, path::Path, process};
+
+use crate::template::crypt::{Key, KEY_FILE, KEY_VAR};
+
+/// Writes a new random key to `.aoc/key`. An existing key is only replaced with `force`, as
+/// files encrypted with it can't be decrypted anymore.
+pub fn handle(force: bool) {
+    if !force && Path::new(KEY_FILE).exists() {
+        eprintln!(
+            "\"{KEY_FILE}\" already exists. Pass --force to replace it, files encrypted with it can't be decrypted afterwards."
+        );
+        process::exit(1);
+    }
+
+    let written = fs::create_dir_all(".aoc")
+        .and_then(|()| fs::write(KEY_FILE, format!("{}\n", Key::generate().to_hex())));
+    if let Err(e) = written {
+        eprintln!("Failed to write key file: {e}");
+        process::exit(1);
+    }
+
+    println!("Created key file \"{KEY_FILE}\"");
+    println!("---");
+    println!("🎄 Keep a copy of the key, e.g. in the {KEY_VAR} secret of your CI: without it, encrypted files are lost.");
+}
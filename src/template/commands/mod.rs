@@ -1,5 +1,12 @@
 pub mod all;
+pub mod bench;
+pub mod decrypt;
 pub mod download;
+pub mod encrypt;
+pub mod examples;
+pub mod generate;
+pub mod keygen;
 pub mod read;
+pub mod reset;
 pub mod scaffold;
 pub mod solve;
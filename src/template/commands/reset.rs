@@ -0,0 +1,188 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    iter,
+    path::{Path, PathBuf},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::commands::scaffold::{self, TemplateArgs};
+use crate::template::{aoc_cli, crypt};
+use crate::Day;
+
+/// Options of the `reset` command.
+#[derive(Debug, Default)]
+pub struct ResetArgs {
+    /// Only print what would happen.
+    pub dry_run: bool,
+    /// Also archive the input and its encrypted copy.
+    pub input: bool,
+    pub template: TemplateArgs,
+}
+
+/// Archives the solution of `day` to `src/archive/<year>/` and restores a fresh module from
+/// the template. The input is kept unless `args.input` is set, then it's archived as well.
+pub fn handle(day: Day, args: &ResetArgs) {
+    let module_path = format!("src/bin/{day}.rs");
+    let Ok(solution) = fs::read_to_string(&module_path) else {
+        eprintln!("There is no solution for day {day}. Try running \"cargo scaffold {day}\".");
+        process::exit(1);
+    };
+    let Some(year) = aoc_cli::get_year() else {
+        eprintln!("AOC_YEAR is not set, it's needed to archive the solution.");
+        process::exit(1);
+    };
+    let module = scaffold::module(day, &args.template).unwrap_or_else(|e| {
+        eprintln!("Failed to load template: {e}");
+        process::exit(1);
+    });
+
+    let verb = |done: &'static str, planned: &'static str| match args.dry_run {
+        true => planned,
+        false => done,
+    };
+
+    let archive_dir = format!("src/archive/{year}");
+    let input_path = PathBuf::from(format!("data/inputs/{day}.txt"));
+    let inputs = match args.input {
+        true => [input_path.clone(), crypt::encrypted_path(&input_path)]
+            .into_iter()
+            .filter(|path| path.exists())
+            .collect(),
+        false => vec![],
+    };
+    let archive = |stem: &str, extension: &str| format!("{archive_dir}/{stem}.{extension}");
+    let stem = free_stem(&format!("{day}-{}", timestamp(SystemTime::now())), |stem| {
+        ["rs", "txt", "txt.enc"]
+            .iter()
+            .any(|extension| Path::new(&archive(stem, extension)).exists())
+    });
+    if !args.dry_run && (solution != module || !inputs.is_empty()) {
+        if let Err(e) = fs::create_dir_all(&archive_dir) {
+            eprintln!("Failed to create archive directory: {e}");
+            process::exit(1);
+        }
+    }
+
+    if solution == module {
+        println!("\"{module_path}\" is a fresh template already, nothing to archive.");
+    } else {
+        let archive_path = archive(&stem, "rs");
+        if !args.dry_run {
+            let archived = create_new(&archive_path, solution.as_bytes())
+                .and_then(|()| fs::write(&module_path, &module));
+            if let Err(e) = archived {
+                eprintln!("Failed to archive the solution: {e}");
+                process::exit(1);
+            }
+        }
+        println!(
+            "{} \"{module_path}\" to \"{archive_path}\"",
+            verb("Archived", "Would archive")
+        );
+        println!(
+            "{} \"{module_path}\" from the template",
+            verb("Restored", "Would restore")
+        );
+    }
+
+    for path in inputs {
+        let extension = match path.extension().is_some_and(|e| e == "enc") {
+            true => "txt.enc",
+            false => "txt",
+        };
+        let archive_path = archive(&stem, extension);
+        if !args.dry_run {
+            let moved = fs::read(&path)
+                .and_then(|data| create_new(&archive_path, &data))
+                .and_then(|()| fs::remove_file(&path));
+            if let Err(e) = moved {
+                eprintln!("Failed to archive the input: {e}");
+                process::exit(1);
+            }
+        }
+        println!(
+            "{} \"{}\" to \"{archive_path}\"",
+            verb("Archived", "Would archive"),
+            path.display()
+        );
+    }
+
+    if args.dry_run {
+        println!("---");
+        println!("🎄 This was a dry run, nothing was changed.");
+    }
+}
+
+/// `stem`, or the first of `stem-2`, `stem-3`, ... that isn't `taken`, so that resetting twice
+/// within a second keeps both solutions.
+fn free_stem(stem: &str, taken: impl Fn(&str) -> bool) -> String {
+    iter::once(stem.to_string())
+        .chain((2..).map(|n| format!("{stem}-{n}")))
+        .find(|stem| !taken(stem))
+        .expect("some suffix to be free")
+}
+
+/// Writes a new file, failing instead of overwriting an existing one.
+fn create_new(path: &str, contents: &[u8]) -> io::Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?
+        .write_all(contents)
+}
+
+/// A UTC `YYYYMMDD-HHMMSS` timestamp, which sorts archived solutions by age.
+fn timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+
+    // the civil date of a day count, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}{month:02}{day:02}-{:02}{:02}{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn suffixes_taken_stems() {
+        assert_eq!(
+            free_stem("01-20231201-050000", |_| false),
+            "01-20231201-050000"
+        );
+        let taken = ["01-20231201-050000", "01-20231201-050000-2"];
+        assert_eq!(
+            free_stem("01-20231201-050000", |stem| taken.contains(&stem)),
+            "01-20231201-050000-3"
+        );
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(timestamp(UNIX_EPOCH), "19700101-000000");
+        let time = UNIX_EPOCH + Duration::from_secs(1_701_406_800);
+        assert_eq!(timestamp(time), "20231201-050000");
+        let time = UNIX_EPOCH + Duration::from_secs(1_709_164_799);
+        assert_eq!(timestamp(time), "20240228-235959");
+    }
+}
@@ -1,18 +1,31 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
+use crate::template::{aoc_cli, metadata};
 use crate::Day;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// Where teams keep their own templates, e.g. `.aoc/templates/grid.rs`. A `default.rs`
+/// there replaces [`MODULE_TEMPLATE`].
+const TEMPLATE_DIR: &str = ".aoc/templates";
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// Templates may use these placeholders:
+/// - `{{day}}`: the day's number, e.g. `7`.
+/// - `{{year}}`: the puzzle year from `AOC_YEAR`.
+/// - `{{title}}`: the puzzle title once its description is downloaded, e.g. `Camel Cards`.
+/// - `{{header}}`: a module doc comment like `//! Day 7: Camel Cards`, if the title is known.
+/// - `{{part_one_type}}`, `{{part_two_type}}`: the answer types, `u32` by default.
+/// - `{{imports}}`: a `use` line for the library modules passed with `--use`.
+const MODULE_TEMPLATE: &str = r#"{{header}}{{imports}}advent_of_code::solution!({{day}});
+
+pub fn part_one(input: &str) -> Option<{{part_one_type}}> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<{{part_two_type}}> {
     None
 }
 
@@ -34,19 +47,134 @@ mod tests {
 }
 "#;
 
+/// Options for filling in the module template.
+#[derive(Debug, Default)]
+pub struct TemplateArgs {
+    /// The name of a template in [`TEMPLATE_DIR`].
+    pub template: Option<String>,
+    /// The answer type of both parts, or of each part if separated by a comma.
+    pub answer_types: Option<String>,
+    /// Comma-separated library modules to import.
+    pub imports: Option<String>,
+}
+
+impl TemplateArgs {
+    fn load_template(&self) -> Result<String, String> {
+        let name = self.template.as_deref().unwrap_or("default");
+        let path = Path::new(TEMPLATE_DIR).join(format!("{name}.rs"));
+        match fs::read_to_string(&path) {
+            Ok(template) => Ok(template),
+            Err(_) if self.template.is_none() => Ok(MODULE_TEMPLATE.to_string()),
+            Err(e) => {
+                let available = fs::read_dir(TEMPLATE_DIR)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|entry| {
+                        let path = entry.ok()?.path();
+                        let name = path.file_stem()?.to_string_lossy().into_owned();
+                        (path.extension()? == "rs").then_some(name)
+                    })
+                    .collect::<Vec<_>>();
+                Err(format!(
+                    "could not read template \"{}\": {e}. Available templates: {}.",
+                    path.display(),
+                    match available.is_empty() {
+                        true => "none".to_string(),
+                        false => available.join(", "),
+                    }
+                ))
+            }
+        }
+    }
+
+    fn placeholders(&self, day: Day) -> Vec<(&'static str, String)> {
+        let (one, two) = match self.answer_types.as_deref() {
+            Some(types) => match types.split_once(',') {
+                Some((one, two)) => (one.trim(), two.trim()),
+                None => (types.trim(), types.trim()),
+            },
+            None => ("u32", "u32"),
+        };
+        let modules = self
+            .imports
+            .iter()
+            .flat_map(|imports| imports.split(','))
+            .map(str::trim)
+            .filter(|module| !module.is_empty())
+            .collect::<Vec<_>>();
+        let imports = match modules[..] {
+            [] => String::new(),
+            [module] => format!("use advent_of_code::{module};\n\n"),
+            _ => format!("use advent_of_code::{{{}}};\n\n", modules.join(", ")),
+        };
+        let title = metadata::title(day).unwrap_or_default();
+        let header = match title.is_empty() {
+            true => String::new(),
+            false => format!("//! Day {}: {title}\n\n", day.into_inner()),
+        };
+        vec![
+            ("day", day.into_inner().to_string()),
+            (
+                "year",
+                aoc_cli::get_year().map_or_else(String::new, |year| year.to_string()),
+            ),
+            ("title", title),
+            ("header", header),
+            ("part_one_type", one.to_string()),
+            ("part_two_type", two.to_string()),
+            ("imports", imports),
+        ]
+    }
+}
+
+/// Replaces the `{{name}}` placeholders in `template`, leaving unknown ones as they are.
+fn render(template: &str, placeholders: &[(&str, String)]) -> String {
+    placeholders
+        .iter()
+        .fold(template.to_string(), |module, (name, value)| {
+            module.replace(&format!("{{{{{name}}}}}"), value)
+        })
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
+/// The contents of a new module for `day`, rendered from the chosen template.
+pub fn module(day: Day, args: &TemplateArgs) -> Result<String, String> {
+    let template = args.load_template()?;
+    Ok(render(&template, &args.placeholders(day)))
+}
+
+pub fn handle(day: Day, args: &TemplateArgs) {
+    let module = match module(day, args) {
+        Ok(module) => module,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
     let input_path = format!("data/inputs/{day}.txt");
     let example_path = format!("data/examples/{day}.txt");
     let module_path = format!("src/bin/{day}.rs");
 
+    // the data folders may be missing, e.g. in a fresh clone, as git doesn't keep empty ones.
+    for dir in ["data/inputs", "data/examples"] {
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("Failed to create folder \"{dir}\": {e}");
+            process::exit(1);
+        }
+    }
+
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
         Err(e) => {
@@ -55,11 +183,7 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -92,3 +216,27 @@ pub fn handle(day: Day) {
     println!("---");
     println!("🎄 Type `cargo solve {}` to run your solution.", day);
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_placeholders() {
+        let args = TemplateArgs {
+            answer_types: Some("u64, String".into()),
+            imports: Some("graph,parse".into()),
+            ..TemplateArgs::default()
+        };
+        let module = render(MODULE_TEMPLATE, &args.placeholders(crate::day!(7)));
+        assert!(module.starts_with(
+            "//! Day 7: Camel Cards\n\nuse advent_of_code::{graph, parse};\n\nadvent_of_code::solution!(7);\n"
+        ));
+        assert!(module.contains("pub fn part_one(input: &str) -> Option<u64> {"));
+        assert!(module.contains("pub fn part_two(input: &str) -> Option<String> {"));
+        assert_eq!(
+            render("{{day}} {{unknown}}", &[("day", "7".into())]),
+            "7 {{unknown}}"
+        );
+    }
+}
@@ -1,8 +1,90 @@
-use std::process::{Command, Stdio};
+use std::path::PathBuf;
+use std::process::{self, Command, ExitStatus, Stdio};
 
+use crate::template::{examples, ANSI_BOLD, ANSI_RESET};
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+/// Visualisation flags that are passed on to the solution, see [`crate::viz`].
+#[derive(Debug, Default)]
+pub struct VizArgs {
+    pub enabled: bool,
+    pub out: Option<PathBuf>,
+    pub delay_ms: Option<u64>,
+}
+
+impl VizArgs {
+    fn push_to(self, cmd_args: &mut Vec<String>) {
+        if self.enabled {
+            cmd_args.push("--viz".to_string());
+        }
+
+        if let Some(out) = self.out {
+            cmd_args.push("--viz-out".to_string());
+            cmd_args.push(out.display().to_string());
+        }
+
+        if let Some(delay_ms) = self.delay_ms {
+            cmd_args.push("--viz-delay".to_string());
+            cmd_args.push(delay_ms.to_string());
+        }
+    }
+}
+
+/// Flags that change how the solution runs, passed on to it.
+#[derive(Debug, Default)]
+pub struct RunArgs {
+    /// Bench each part, see [`crate::template::runner`].
+    pub time: bool,
+    /// Print `tracing` output.
+    pub trace: bool,
+    /// The name of a variant to run instead of the default one.
+    pub variant: Option<String>,
+}
+
+impl RunArgs {
+    fn push_to(self, cmd_args: &mut Vec<String>) {
+        if self.time {
+            cmd_args.push("--time".to_string());
+        }
+
+        if self.trace {
+            cmd_args.push("--trace".to_string());
+        }
+
+        if let Some(variant) = self.variant {
+            cmd_args.push("--variant".to_string());
+            cmd_args.push(variant);
+        }
+    }
+}
+
+/// Where the solution reads its input from.
+#[derive(Debug, Default)]
+pub enum InputSource {
+    /// `data/inputs/<day>.txt`.
+    #[default]
+    Day,
+    /// Any file, or stdin for `-`.
+    Path(PathBuf),
+    /// The day's examples, or the one with the given name. See [`examples::find`].
+    Examples(Option<String>),
+}
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    run_args: RunArgs,
+    viz: VizArgs,
+    submit_part: Option<u8>,
+    input: InputSource,
+) {
+    if !matches!(input, InputSource::Day) && submit_part.is_some() {
+        eprintln!(
+            "Answers for other inputs can't be submitted, remove --input or --example to submit."
+        );
+        process::exit(1);
+    }
+
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if release {
@@ -16,16 +98,56 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
-        cmd_args.push("--time".to_string());
+    run_args.push_to(&mut cmd_args);
+    viz.push_to(&mut cmd_args);
+
+    match input {
+        InputSource::Day => {
+            run(&cmd_args);
+        }
+        InputSource::Path(path) => {
+            // `-` reads the input from stdin, which the child inherits.
+            cmd_args.push("--input".to_string());
+            cmd_args.push(path.display().to_string());
+            run(&cmd_args);
+        }
+        InputSource::Examples(name) => {
+            let examples = examples::find(day, name.as_deref());
+            if examples.is_empty() {
+                match name {
+                    Some(name) => eprintln!("There is no example \"{name}\" for day {day}."),
+                    None => eprintln!("There are no examples for day {day}."),
+                }
+                process::exit(1);
+            }
+
+            for (i, example) in examples.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                println!("{ANSI_BOLD}Example {}{ANSI_RESET}", example.file_name());
+                println!("------");
+
+                let mut cmd_args = cmd_args.clone();
+                cmd_args.push("--example".to_string());
+                cmd_args.push(example.path.display().to_string());
+                if let [part] = example.parts()[..] {
+                    cmd_args.push("--part".to_string());
+                    cmd_args.push(part.to_string());
+                }
+                run(&cmd_args);
+            }
+        }
     }
+}
 
+pub(crate) fn run(cmd_args: &[String]) -> ExitStatus {
     let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+        .args(cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    cmd.wait().unwrap()
 }
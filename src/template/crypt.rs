@@ -0,0 +1,220 @@
+//! Encryption of personal puzzle data, so that inputs and puzzle descriptions can be
+//! committed without publishing them.
+//!
+//! The key is a random 256-bit key written as 64 hex digits, generated with `cargo keygen`. It
+//! is read from the `AOC_DATA_KEY` environment variable, or else from the `.aoc/key` file. A
+//! random key can't be guessed offline like a passphrase could, which matters as the encrypted
+//! files are meant to be public.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, OsRng},
+    ChaCha20Poly1305, KeyInit, Nonce,
+};
+
+use crate::Day;
+
+pub const KEY_VAR: &str = "AOC_DATA_KEY";
+pub const KEY_FILE: &str = ".aoc/key";
+
+/// Starts every encrypted file, so that its format can change later.
+const MAGIC: &[u8] = b"aoc-enc1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum CryptError {
+    NoKey,
+    InvalidKey,
+    Io(PathBuf, io::Error),
+    NotEncrypted(PathBuf),
+    WrongKey(PathBuf),
+}
+
+impl Display for CryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptError::NoKey => write!(
+                f,
+                "no key found: set {KEY_VAR} or run \"cargo keygen\" to create \"{KEY_FILE}\"."
+            ),
+            CryptError::InvalidKey => write!(
+                f,
+                "the key must be 64 hex digits, run \"cargo keygen\" to generate one."
+            ),
+            CryptError::Io(path, e) => write!(f, "could not access \"{}\": {e}", path.display()),
+            CryptError::NotEncrypted(path) => {
+                write!(f, "\"{}\" is not an encrypted file.", path.display())
+            }
+            CryptError::WrongKey(path) => write!(
+                f,
+                "could not decrypt \"{}\": wrong key or corrupted file.",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CryptError {}
+
+pub struct Key([u8; 32]);
+
+impl Key {
+    /// A new random key.
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// Parses a key written as 64 hex digits, ignoring surrounding whitespace.
+    pub fn from_hex(text: &str) -> Result<Self, CryptError> {
+        let text = text.trim();
+        if text.len() != 64 || !text.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(CryptError::InvalidKey);
+        }
+        let mut key = [0; 32];
+        for (byte, i) in key.iter_mut().zip((0..64).step_by(2)) {
+            *byte = u8::from_str_radix(&text[i..i + 2], 16).map_err(|_| CryptError::InvalidKey)?;
+        }
+        Ok(Self(key))
+    }
+
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Loads the key from the environment, falling back to the key file.
+    pub fn load() -> Result<Self, CryptError> {
+        let text = match env::var(KEY_VAR) {
+            Ok(text) => text,
+            Err(_) => match fs::read_to_string(KEY_FILE) {
+                Ok(text) => text,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(CryptError::NoKey),
+                Err(e) => return Err(CryptError::Io(KEY_FILE.into(), e)),
+            },
+        };
+        Self::from_hex(&text)
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+/// The personal files of a day: its input and its puzzle description.
+#[must_use]
+pub fn data_files(day: Day) -> [PathBuf; 2] {
+    [
+        format!("data/inputs/{day}.txt").into(),
+        format!("data/puzzles/{day}.md").into(),
+    ]
+}
+
+/// The path of the encrypted copy of `path`, e.g. `data/inputs/01.txt.enc`.
+#[must_use]
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".enc");
+    path.into()
+}
+
+/// Encrypts `plaintext` with a random nonce, so equal files don't give equal bytes.
+#[must_use]
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = key
+        .cipher()
+        .encrypt(&nonce, plaintext)
+        .expect("plaintext should fit in memory");
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+/// Decrypts the contents of the encrypted file at `path`.
+pub fn decrypt(key: &Key, data: &[u8], path: &Path) -> Result<Vec<u8>, CryptError> {
+    let rest = data
+        .strip_prefix(MAGIC)
+        .filter(|rest| rest.len() >= NONCE_LEN)
+        .ok_or_else(|| CryptError::NotEncrypted(path.into()))?;
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    key.cipher()
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptError::WrongKey(path.into()))
+}
+
+/// Reads and decrypts the file at `path` with the loaded key.
+pub fn read_encrypted(path: &Path) -> Result<String, CryptError> {
+    let key = Key::load()?;
+    let data = fs::read(path).map_err(|e| CryptError::Io(path.into(), e))?;
+    let plaintext = decrypt(&key, &data, path)?;
+    String::from_utf8(plaintext).map_err(|_| CryptError::WrongKey(path.into()))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let key = Key::generate();
+        let data = encrypt(&key, b"467..114..\n...*......\n");
+        assert!(!data.windows(3).any(|w| w == b"114"));
+        let path = Path::new("03.txt.enc");
+        assert_eq!(
+            decrypt(&key, &data, path).unwrap(),
+            b"467..114..\n...*......\n"
+        );
+    }
+
+    #[test]
+    fn uses_random_nonces() {
+        let key = Key::generate();
+        let (one, two) = (encrypt(&key, b"input"), encrypt(&key, b"input"));
+        assert_ne!(one, two);
+        let path = Path::new("01.txt.enc");
+        assert_eq!(
+            decrypt(&key, &one, path).unwrap(),
+            decrypt(&key, &two, path).unwrap()
+        );
+    }
+
+    #[test]
+    fn parses_hex_keys() {
+        let key = Key::generate();
+        assert_eq!(
+            Key::from_hex(&format!(" {}\n", key.to_hex())).unwrap().0,
+            key.0
+        );
+        assert_eq!(Key::from_hex(&"0f".repeat(32)).unwrap().0, [0x0f; 32]);
+        for text in ["", "hunter2", &"0f".repeat(31), &"zz".repeat(32)] {
+            assert!(matches!(Key::from_hex(text), Err(CryptError::InvalidKey)));
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_keys_and_plain_files() {
+        let data = encrypt(&Key::generate(), b"input");
+        let path = Path::new("01.txt.enc");
+        let other = Key::generate();
+        assert!(matches!(
+            decrypt(&other, &data, path),
+            Err(CryptError::WrongKey(_))
+        ));
+        assert!(matches!(
+            decrypt(&other, b"input", path),
+            Err(CryptError::NotEncrypted(_))
+        ));
+    }
+
+    #[test]
+    fn appends_extension() {
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/01.txt")),
+            Path::new("data/inputs/01.txt.enc")
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Example files of a day and their recorded answers.
+//!
+//! A day's main example is `data/examples/NN.txt`; further examples are named `NN-<name>.txt`.
+//! An example named after a part, like `01-2.txt`, only applies to that part (see
+//! [`crate::template::read_file_part`]). Answers are recorded next to an example, e.g. in
+//! `01-2.answers`, as lines like `Part 2: 281`.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::Day;
+
+const FOLDER: &str = "data/examples";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub path: PathBuf,
+    /// The part this example is restricted to, if it's named after one.
+    pub part: Option<u8>,
+    /// The recorded answers for parts 1 and 2.
+    pub answers: [Option<String>; 2],
+}
+
+impl Example {
+    fn new(path: PathBuf, day: Day) -> Self {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let part = stem
+            .strip_prefix(&format!("{day}-"))
+            .and_then(|name| name.parse().ok())
+            .filter(|part| matches!(part, 1 | 2));
+        let answers = read_answers(&path);
+        Self {
+            path,
+            part,
+            answers,
+        }
+    }
+
+    #[must_use]
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// The parts to run: the one the example is named after, else those with recorded
+    /// answers, else both.
+    #[must_use]
+    pub fn parts(&self) -> Vec<u8> {
+        if let Some(part) = self.part {
+            return vec![part];
+        }
+        let recorded = (1..=2)
+            .filter(|&part| self.answers[usize::from(part - 1)].is_some())
+            .collect::<Vec<_>>();
+        match recorded.is_empty() {
+            true => vec![1, 2],
+            false => recorded,
+        }
+    }
+}
+
+/// The examples of `day`, main example first. A `name` selects a single one: `2`, `01-2` and
+/// `01-2.txt` all name `01-2.txt`, and `01` names the main example.
+#[must_use]
+pub fn find(day: Day, name: Option<&str>) -> Vec<Example> {
+    let mut paths = fs::read_dir(FOLDER)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            let Some(stem) = file_name.strip_suffix(".txt") else {
+                return false;
+            };
+            stem == day.to_string() || stem.starts_with(&format!("{day}-"))
+        })
+        .collect::<Vec<_>>();
+    // `01.txt` sorts before `01-2.txt` by its stem.
+    paths.sort_by_key(|path| path.file_stem().map(ToOwned::to_owned));
+
+    if let Some(name) = name {
+        let name = name.strip_suffix(".txt").unwrap_or(name);
+        let stem = match name.starts_with(&day.to_string()) {
+            true => name.to_string(),
+            false => format!("{day}-{name}"),
+        };
+        paths.retain(|path| path.file_stem().is_some_and(|s| s == stem.as_str()));
+    }
+    paths
+        .into_iter()
+        .map(|path| Example::new(path, day))
+        .collect()
+}
+
+/// The file recording the answers of the example at `path`.
+#[must_use]
+pub fn answers_path(path: &Path) -> PathBuf {
+    path.with_extension("answers")
+}
+
+/// The answers recorded for the example at `path`, if any.
+#[must_use]
+pub fn read_answers(path: &Path) -> [Option<String>; 2] {
+    let text = fs::read_to_string(answers_path(path)).unwrap_or_default();
+    parse_answers(&text)
+}
+
+fn parse_answers(text: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    for line in text.lines() {
+        let Some((part, answer)) = line.split_once(':') else {
+            continue;
+        };
+        match part.trim() {
+            "Part 1" => answers[0] = Some(answer.trim().to_string()),
+            "Part 2" => answers[1] = Some(answer.trim().to_string()),
+            _ => {}
+        }
+    }
+    answers
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_answers() {
+        assert_eq!(
+            parse_answers("Part 1: 4361\nPart 2: 467835\n"),
+            [Some("4361".to_string()), Some("467835".to_string())]
+        );
+        assert_eq!(parse_answers("Part 2: 6\n"), [None, Some("6".to_string())]);
+        assert_eq!(parse_answers(""), [None, None]);
+    }
+
+    #[test]
+    fn restricts_parts() {
+        let example = |part, answers| Example {
+            path: PathBuf::new(),
+            part,
+            answers,
+        };
+        assert_eq!(example(None, [None, None]).parts(), [1, 2]);
+        assert_eq!(example(Some(2), [None, None]).parts(), [2]);
+        assert_eq!(example(None, [None, Some("6".into())]).parts(), [2]);
+    }
+
+    #[test]
+    fn finds_examples() {
+        let day = crate::day!(1);
+        let names = |name| {
+            find(day, name)
+                .iter()
+                .map(Example::file_name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(None), ["01.txt", "01-2.txt"]);
+        assert_eq!(names(Some("2")), ["01-2.txt"]);
+        assert_eq!(names(Some("01-2.txt")), ["01-2.txt"]);
+        assert_eq!(names(Some("01")), ["01.txt"]);
+        assert_eq!(find(day, Some("2"))[0].part, Some(2));
+    }
+}
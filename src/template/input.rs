@@ -0,0 +1,205 @@
+//! Reading and sanity checks of input files.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::crypt::{self, CryptError};
+use crate::{generate, Day};
+
+/// Starts of responses that a failed download saves instead of the input.
+const ERROR_PAGES: [&str; 4] = [
+    "<!doctype html",
+    "<html",
+    "puzzle inputs differ by user",
+    "please don't repeatedly request this endpoint",
+];
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound(PathBuf),
+    Io(PathBuf, io::Error),
+    Empty(PathBuf),
+    ErrorPage(PathBuf),
+    Crypt(CryptError),
+}
+
+impl InputError {
+    fn path(&self) -> Option<&Path> {
+        match self {
+            InputError::NotFound(path)
+            | InputError::Io(path, _)
+            | InputError::Empty(path)
+            | InputError::ErrorPage(path) => Some(path),
+            InputError::Crypt(_) => None,
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound(path) => write!(f, "\"{}\" does not exist.", path.display()),
+            InputError::Io(path, e) => write!(f, "could not read \"{}\": {e}", path.display()),
+            InputError::Empty(path) => write!(f, "\"{}\" is empty.", path.display()),
+            InputError::ErrorPage(path) => write!(
+                f,
+                "\"{}\" contains an error page instead of an input, the download probably failed.",
+                path.display()
+            ),
+            InputError::Crypt(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl From<CryptError> for InputError {
+    fn from(e: CryptError) -> Self {
+        InputError::Crypt(e)
+    }
+}
+
+/// An [`InputError`] for the file of `day`, with a hint on how to fix it.
+#[derive(Debug)]
+pub struct ReadError {
+    pub day: Day,
+    pub error: InputError,
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)?;
+        let in_inputs = self
+            .error
+            .path()
+            .is_some_and(|path| path.parent().is_some_and(|dir| dir.ends_with("inputs")));
+        if in_inputs && !matches!(self.error, InputError::Io(..)) {
+            let day = self.day;
+            write!(f, "\nTry running \"cargo download {day}\"")?;
+            if generate::has_generator(day) {
+                write!(f, " or \"cargo generate {day}\"")?;
+            }
+            write!(f, ".")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+/// Reads the file at `path`, or decrypts its encrypted copy if the file is missing or blank,
+/// e.g. a freshly scaffolded placeholder, and normalises it. See [`normalize`]. Inputs are
+/// also [`check`]ed; examples may be empty while a day is in progress.
+pub fn read(path: &Path, is_input: bool, trim: bool) -> Result<String, InputError> {
+    let encrypted = crypt::encrypted_path(path);
+    let text = match fs::read_to_string(path) {
+        Ok(text) if text.trim().is_empty() && encrypted.exists() => {
+            crypt::read_encrypted(&encrypted)?
+        }
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => match encrypted.exists() {
+            true => crypt::read_encrypted(&encrypted)?,
+            false => return Err(InputError::NotFound(path.into())),
+        },
+        Err(e) => return Err(InputError::Io(path.into(), e)),
+    };
+    if is_input {
+        check(&text, path)?;
+    }
+    Ok(normalize(&text, trim))
+}
+
+/// Reads the whole standard input, [`check`]ed and normalised like an input file.
+pub fn read_stdin(trim: bool) -> Result<String, InputError> {
+    let path = Path::new("<stdin>");
+    let text = io::read_to_string(io::stdin()).map_err(|e| InputError::Io(path.into(), e))?;
+    check(&text, path)?;
+    Ok(normalize(&text, trim))
+}
+
+/// Whether `text` looks like an input: not blank and not an error page.
+pub fn check(text: &str, path: &Path) -> Result<(), InputError> {
+    let start = text.trim_start().to_lowercase();
+    if start.is_empty() {
+        Err(InputError::Empty(path.into()))
+    } else if ERROR_PAGES.iter().any(|page| start.starts_with(page)) {
+        Err(InputError::ErrorPage(path.into()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Converts CRLF line endings to LF and drops trailing blank lines, keeping a final newline
+/// if there was one. Trimming also drops that newline and any whitespace around the text.
+#[must_use]
+pub fn normalize(text: &str, trim: bool) -> String {
+    let text = text.replace("\r\n", "\n");
+    if trim {
+        return text.trim().to_string();
+    }
+    let content = text.trim_end_matches(|c: char| c.is_whitespace());
+    // the last line keeps its trailing spaces, only the blank lines after it go.
+    let end = match text[content.len()..].find('\n') {
+        Some(newline) => content.len() + newline + 1,
+        None => text.len(),
+    };
+    text[..end].to_string()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_line_endings_and_blank_lines() {
+        assert_eq!(normalize("a\r\nb\r\n\r\n\n", false), "a\nb\n");
+        assert_eq!(normalize("a\nb", false), "a\nb");
+        assert_eq!(normalize("  a\nb  \n\n", false), "  a\nb  \n");
+        assert_eq!(normalize("\n  a\r\nb  \n\n", true), "a\nb");
+    }
+
+    #[test]
+    fn rejects_blank_files_and_error_pages() {
+        let path = Path::new("data/inputs/01.txt");
+        assert!(check("1abc2\n", path).is_ok());
+        assert!(matches!(check(" \n\n", path), Err(InputError::Empty(_))));
+        assert!(matches!(
+            check("<!DOCTYPE html>\n<html lang=\"en-us\">", path),
+            Err(InputError::ErrorPage(_))
+        ));
+        assert!(matches!(
+            check(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+                path
+            ),
+            Err(InputError::ErrorPage(_))
+        ));
+    }
+
+    #[test]
+    fn suggests_downloading_inputs() {
+        let error = ReadError {
+            day: crate::day!(8),
+            error: InputError::Empty("/aoc/data/inputs/08.txt".into()),
+        };
+        assert_eq!(
+            error.to_string(),
+            "\"/aoc/data/inputs/08.txt\" is empty.\nTry running \"cargo download 08\" or \"cargo generate 08\"."
+        );
+        let error = ReadError {
+            day: crate::day!(8),
+            error: InputError::Empty("/aoc/data/examples/08.txt".into()),
+        };
+        assert_eq!(error.to_string(), "\"/aoc/data/examples/08.txt\" is empty.");
+        let error = ReadError {
+            day: crate::day!(25),
+            error: InputError::NotFound("/aoc/data/inputs/25.txt".into()),
+        };
+        assert_eq!(
+            error.to_string(),
+            "\"/aoc/data/inputs/25.txt\" does not exist.\nTry running \"cargo download 25\"."
+        );
+    }
+}
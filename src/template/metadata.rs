@@ -0,0 +1,94 @@
+//! Facts about a day that are kept in the repository, in `data/metadata/NN.txt` as
+//! `key: value` lines. Unlike the puzzle description, they aren't personal and don't need
+//! to be encrypted.
+use std::{fmt::Write, fs, io, path::Path};
+
+use crate::template::{input, puzzle};
+use crate::Day;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DayMetadata {
+    /// The puzzle title, e.g. `Pipe Maze`.
+    pub title: Option<String>,
+}
+
+impl DayMetadata {
+    fn parse(text: &str) -> Self {
+        let mut metadata = Self::default();
+        for (key, value) in text.lines().filter_map(|line| line.split_once(':')) {
+            if key.trim() == "title" && !value.trim().is_empty() {
+                metadata.title = Some(value.trim().to_string());
+            }
+        }
+        metadata
+    }
+
+    fn format(&self) -> String {
+        let mut text = String::new();
+        if let Some(title) = &self.title {
+            let _ = writeln!(text, "title: {title}");
+        }
+        text
+    }
+}
+
+fn path(day: Day) -> String {
+    format!("data/metadata/{day}.txt")
+}
+
+/// The metadata of `day`, with the title taken from the puzzle description if it isn't
+/// recorded yet.
+#[must_use]
+pub fn load(day: Day) -> DayMetadata {
+    let mut metadata = DayMetadata::parse(&fs::read_to_string(path(day)).unwrap_or_default());
+    if metadata.title.is_none() {
+        metadata.title = puzzle_title(day);
+    }
+    metadata
+}
+
+#[must_use]
+pub fn title(day: Day) -> Option<String> {
+    load(day).title
+}
+
+pub fn save(day: Day, metadata: &DayMetadata) -> io::Result<()> {
+    fs::create_dir_all("data/metadata")?;
+    fs::write(path(day), metadata.format())
+}
+
+/// Records the title of the downloaded puzzle description of `day`, returning it.
+pub fn update_from_puzzle(day: Day) -> io::Result<Option<String>> {
+    let Some(title) = puzzle_title(day) else {
+        return Ok(None);
+    };
+    let mut metadata = load(day);
+    metadata.title = Some(title.clone());
+    save(day, &metadata)?;
+    Ok(Some(title))
+}
+
+fn puzzle_title(day: Day) -> Option<String> {
+    let text = input::read(Path::new(&format!("data/puzzles/{day}.md")), false, false).ok()?;
+    puzzle::title(&text)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let metadata = DayMetadata {
+            title: Some("Pipe Maze".into()),
+        };
+        assert_eq!(metadata.format(), "title: Pipe Maze\n");
+        assert_eq!(DayMetadata::parse(&metadata.format()), metadata);
+        assert_eq!(DayMetadata::parse("title:\n"), DayMetadata::default());
+    }
+
+    #[test]
+    fn loads_recorded_titles() {
+        assert_eq!(title(crate::day!(10)).as_deref(), Some("Pipe Maze"));
+    }
+}
@@ -1,8 +1,14 @@
 use crate::Day;
-use std::{env, fs};
+use input::ReadError;
+use std::env;
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod crypt;
+pub mod examples;
+pub mod input;
+pub mod metadata;
+pub mod puzzle;
 pub mod readme_benchmarks;
 pub mod runner;
 
@@ -10,39 +16,79 @@ pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file to a string, with CRLF line endings converted and
+/// trailing blank lines dropped.
+///
+/// # Panics
+/// If the file is missing. Inputs also must not be blank or an error page from a failed download.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Like [`read_file`], but also trims whitespace around the text, including the final newline.
+#[must_use]
+pub fn read_file_trimmed(folder: &str, day: Day) -> String {
+    read_data(folder, &format!("{day}.txt"), day, true).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    read_data(folder, &format!("{day}-{part}.txt"), day, false).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Like [`read_file`], but returns the error, which includes the resolved path and a hint.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, ReadError> {
+    read_data(folder, &format!("{day}.txt"), day, false)
+}
+
+fn read_data(folder: &str, file_name: &str, day: Day, trim: bool) -> Result<String, ReadError> {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = cwd.join("data").join(folder).join(file_name);
+    input::read(&filepath, folder == "inputs", trim).map_err(|error| ReadError { day, error })
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// Parts may have several named variants, e.g. a naive and a fast one, which `solve --variant`
+/// picks from and `bench --compare-variants` compares. The first variant is the default:
+///
+/// ```ignore
+/// advent_of_code::solution!(5, part_two: [ranges => part_two, bruteforce => part_two_bruteforce]);
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
+        advent_of_code::solution!(@main $day, [part_one => part_one], [part_two => part_two]);
+    };
+    ($day:expr, part_one: [$($one:ident => $f1:expr),+ $(,)?] $(,)?) => {
+        advent_of_code::solution!(@main $day, [$($one => $f1),+], [part_two => part_two]);
+    };
+    ($day:expr, part_two: [$($two:ident => $f2:expr),+ $(,)?] $(,)?) => {
+        advent_of_code::solution!(@main $day, [part_one => part_one], [$($two => $f2),+]);
+    };
+    (
+        $day:expr,
+        part_one: [$($one:ident => $f1:expr),+ $(,)?],
+        part_two: [$($two:ident => $f2:expr),+ $(,)?] $(,)?
+    ) => {
+        advent_of_code::solution!(@main $day, [$($one => $f1),+], [$($two => $f2),+]);
+    };
+    (@main $day:expr, [$($one:ident => $f1:expr),+], [$($two:ident => $f2:expr),+]) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            init_tracing();
+            let one = [$((stringify!($one), $f1 as fn(&str) -> _)),+];
+            let two = [$((stringify!($two), $f2 as fn(&str) -> _)),+];
+            check_variant(&[&[$(stringify!($one)),+], &[$(stringify!($two)),+]]);
+            let input = read_input(DAY);
+            run_variants(&one, &input, DAY, 1);
+            run_variants(&two, &input, DAY, 2);
+            advent_of_code::viz::finish();
         }
     };
 }
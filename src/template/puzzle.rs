@@ -0,0 +1,255 @@
+//! Parsing of downloaded puzzle descriptions (`data/puzzles/NN.md`).
+//!
+//! aoc-cli converts the puzzle page to markdown, but descriptions saved from the page
+//! itself are HTML; both are understood. Examples are the `<pre><code>` blocks (fenced
+//! blocks in markdown), and a part's answer for its example is the last emphasised code
+//! span of its description, like `<code><em>142</em></code>` or `` `*142*` ``.
+
+/// The description of one part of a puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartDescription {
+    /// The code blocks in order, each with the position where it starts.
+    pub blocks: Vec<(usize, String)>,
+    /// The answer for the example, with the position where it's given.
+    pub answer: Option<(usize, String)>,
+}
+
+impl PartDescription {
+    /// The index of the block the answer most likely belongs to: the last one before it.
+    #[must_use]
+    pub fn likely_block(&self) -> Option<usize> {
+        let answer_at = self.answer.as_ref().map_or(usize::MAX, |answer| answer.0);
+        match self.blocks.iter().rposition(|block| block.0 < answer_at) {
+            Some(i) => Some(i),
+            None if self.blocks.is_empty() => None,
+            None => Some(0),
+        }
+    }
+
+    /// Whether more than one block comes before the answer, so the likely block is a guess.
+    #[must_use]
+    pub fn is_ambiguous(&self) -> bool {
+        self.likely_block().is_some_and(|i| i > 0)
+    }
+}
+
+/// The title of the puzzle, from its heading like `--- Day 10: Pipe Maze ---`.
+#[must_use]
+pub fn title(text: &str) -> Option<String> {
+    let heading = &text[text.find("--- Day ")? + "--- Day ".len()..];
+    let heading = &heading[..heading.find(" ---")?];
+    let (_, title) = heading.split_once(": ")?;
+    Some(unescape(&strip_tags(title.trim()))).filter(|title| !title.is_empty())
+}
+
+/// The descriptions of both parts, the second one only once part one is solved.
+#[must_use]
+pub fn parse(text: &str) -> Vec<PartDescription> {
+    let (one, two) = match text.find("--- Part Two ---") {
+        Some(i) => (&text[..i], Some(&text[i..])),
+        None => (text, None),
+    };
+    [Some(one), two]
+        .into_iter()
+        .flatten()
+        .map(parse_part)
+        .collect()
+}
+
+fn parse_part(text: &str) -> PartDescription {
+    let mut part = PartDescription::default();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let at = text.len() - rest.len();
+        if let Some(block) = rest.strip_prefix("<pre><code>") {
+            let end = block.find("</code></pre>").unwrap_or(block.len());
+            part.blocks.push((at, strip_tags(&block[..end])));
+            rest = &block[end..];
+        } else if let Some(block) = rest.strip_prefix("```").filter(|_| starts_line(text, at)) {
+            // the opening fence may name a language, the block starts on the next line.
+            let block = block.split_once('\n').map_or("", |(_, block)| block);
+            let end = block.find("```").unwrap_or(block.len());
+            part.blocks.push((at, block[..end].to_string()));
+            rest = block[end..].strip_prefix("```").unwrap_or(&block[end..]);
+        } else if let Some((len, span)) = code_span(rest) {
+            if let Some(answer) = emphasised(text, at, len, span) {
+                part.answer = Some((at, answer));
+            }
+            rest = &rest[len..];
+        } else if rest.starts_with("Your puzzle answer was") {
+            // the personal answers of solved parts are not the example's.
+            let end = rest.find('\n').unwrap_or(rest.len());
+            rest = &rest[end..];
+        } else {
+            let next = rest.chars().next().map_or(1, char::len_utf8);
+            rest = &rest[next..];
+        }
+    }
+    part
+}
+
+fn starts_line(text: &str, at: usize) -> bool {
+    at == 0 || text[..at].ends_with('\n')
+}
+
+/// An inline code span at the start of `text`: its length and its contents.
+fn code_span(text: &str) -> Option<(usize, &str)> {
+    let (open, close) = match text.starts_with('`') {
+        true => ("`", "`"),
+        false => ("<code>", "</code>"),
+    };
+    let inner = text.strip_prefix(open)?;
+    let end = inner
+        .find(close)
+        .filter(|&end| !inner[..end].contains('\n'))?;
+    Some((open.len() + end + close.len(), &inner[..end]))
+}
+
+/// The contents of the code span at `at` if it's emphasised, inside or around.
+fn emphasised(text: &str, at: usize, len: usize, span: &str) -> Option<String> {
+    let inside = ["<em>", "**", "*"]
+        .iter()
+        .zip(["</em>", "**", "*"])
+        .find_map(|(open, close)| span.strip_prefix(open)?.strip_suffix(close));
+    let around = ["<em>", "**", "*"]
+        .iter()
+        .zip(["</em>", "**", "*"])
+        .any(|(open, close)| text[..at].ends_with(open) && text[at + len..].starts_with(close));
+    let answer = match (inside, around) {
+        (Some(inside), _) => inside,
+        (None, true) => span,
+        (None, false) => return None,
+    };
+    Some(unescape(&strip_tags(answer))).filter(|answer| !answer.is_empty())
+}
+
+/// Drops HTML tags (e.g. emphasis inside examples) and decodes the usual entities.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        match rest[start..].find('>') {
+            Some(end) => rest = &rest[start + end + 1..],
+            None => {
+                text.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    text.push_str(rest);
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Removes the backslashes markdown escapes special characters with.
+fn unescape(text: &str) -> String {
+    let mut chars = text.chars();
+    let mut unescaped = String::with_capacity(text.len());
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    const HTML: &str = "<article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+</code></pre>
+<p>In this example, the calibration values of these four lines are <code>12</code>, <code>38</code>, <code>15</code>, and <code>77</code>. Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54331</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<p>For example:</p>
+<pre><code>two1nine
+<em>eight</em>wothree
+</code></pre>
+<p>Adding these together produces <code><em>281</em></code>.</p>
+</article>";
+
+    const MARKDOWN: &str = "\\--- Day 10: Pipe Maze ---
+----------
+
+For example:
+
+```
+.....
+.S-7.
+.|.|.
+.L-J.
+.....
+
+```
+
+Here are the distances:
+
+```
+.....
+.012.
+.1.3.
+.234.
+.....
+
+```
+
+The farthest point is `*4*` steps away, not `4` or *`5`*.
+
+Your puzzle answer was `6864`.
+";
+
+    #[test]
+    fn parses_html() {
+        let parts = parse(HTML);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].blocks.len(), 1);
+        assert_eq!(
+            parts[0].blocks[0].1,
+            "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n"
+        );
+        assert_eq!(parts[0].answer.as_ref().unwrap().1, "142");
+        assert_eq!(parts[1].blocks[0].1, "two1nine\neightwothree\n");
+        assert_eq!(parts[1].answer.as_ref().unwrap().1, "281");
+        assert_eq!(parts[0].likely_block(), Some(0));
+        assert!(!parts[0].is_ambiguous());
+    }
+
+    #[test]
+    fn parses_markdown() {
+        let parts = parse(MARKDOWN);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].blocks.len(), 2);
+        assert_eq!(
+            parts[0].blocks[0].1,
+            ".....\n.S-7.\n.|.|.\n.L-J.\n.....\n\n"
+        );
+        // the last emphasised span wins, the personal answer is skipped.
+        assert_eq!(parts[0].answer.as_ref().unwrap().1, "5");
+        assert_eq!(parts[0].likely_block(), Some(1));
+        assert!(parts[0].is_ambiguous());
+    }
+
+    #[test]
+    fn finds_titles() {
+        assert_eq!(title(HTML).as_deref(), Some("Trebuchet?!"));
+        assert_eq!(title(MARKDOWN).as_deref(), Some("Pipe Maze"));
+        assert_eq!(title("For example:"), None);
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(strip_tags("&lt;<em>x</em>&gt; &amp;lt;"), "<x> &lt;");
+        assert_eq!(unescape("\\*\\_a"), "*_a");
+    }
+}
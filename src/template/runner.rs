@@ -1,27 +1,235 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::{aoc_cli, examples, input, try_read_file, ANSI_ITALIC, ANSI_RESET};
+use crate::{memo, ocr, viz, Day};
 use std::fmt::Display;
 use std::io::{stdout, Write};
+use std::path::Path;
 use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
+use tracing::info;
 
 use super::ANSI_BOLD;
 
+/// Enables `tracing` output (e.g. [`crate::memo::Memo`] statistics) when the solution runs with `--trace`.
+pub fn init_tracing() {
+    if env::args().any(|x| x == "--trace") {
+        tracing_subscriber::fmt().with_target(false).init();
+    }
+}
+
+/// Reads the input passed with `--input <path>`, or standard input for `--input -`, the
+/// example passed with `--example <path>`, and `data/inputs/<day>.txt` otherwise. Exits with
+/// an error message if it is unusable.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+    let value = |name: &str| {
+        let i = args.iter().position(|x| x == name)?;
+        Some(args.get(i + 1).map(String::as_str).unwrap_or_else(|| {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 {name} <path>");
+            process::exit(1);
+        }))
+    };
+    let input = match (value("--input"), value("--example")) {
+        (Some("-"), _) => input::read_stdin(false).map_err(|e| e.to_string()),
+        (Some(path), _) => {
+            let path = env::current_dir().unwrap().join(path);
+            input::read(&path, true, false).map_err(|e| e.to_string())
+        }
+        (None, Some(path)) => input::read(Path::new(path), false, false).map_err(|e| e.to_string()),
+        (None, None) => try_read_file("inputs", day).map_err(|e| e.to_string()),
+    };
+    input.unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+/// Runs and prints one part. With `--part <n>`, other parts are skipped. With `--example`,
+/// the result is checked against the answer recorded for the example.
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    let args: Vec<String> = env::args().collect();
+    let value = |name: &str| {
+        let i = args.iter().position(|x| x == name)?;
+        args.get(i + 1)
+    };
+    if value("--part").is_some_and(|only| *only != part.to_string()) {
+        return;
+    }
+    let expected = value("--example")
+        .and_then(|path| examples::read_answers(Path::new(path))[usize::from(part - 1)].clone());
+
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(func, input, |result| {
+        print_result(result, &part_str, "", "")
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let check = expected.map_or_else(String::new, |expected| check_result(&result, &expected));
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&duration, samples),
+        &check,
+    );
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Named implementations of one part, registered with [`crate::solution!`]. The first one is
+/// the default.
+pub type Variants<T> = [(&'static str, fn(&str) -> Option<T>)];
+
+/// Runs the variant of a part picked with `--variant <name>`, or its default if it has no
+/// variant of that name. With `--compare-variants`, all variants are run and compared instead.
+pub fn run_variants<T: Display>(variants: &Variants<T>, input: &str, day: Day, part: u8) {
+    if env::args().any(|x| x == "--compare-variants") {
+        compare_variants(variants, input, part);
+        return;
+    }
+    let chosen =
+        variant_arg(usize::from(part)).and_then(|name| variants.iter().find(|(n, _)| *n == name));
+    let (_, func) = chosen.unwrap_or(&variants[0]);
+    run_part(func, input, day, part);
+}
+
+/// Exits with an error if the variant passed with `--variant` isn't one of any part.
+pub fn check_variant(parts: &[&[&str]]) {
+    let Some(name) = variant_arg(0) else {
+        return;
+    };
+    let found = (1..)
+        .zip(parts)
+        .any(|(part, names)| variant_arg(part).is_some_and(|name| names.contains(&name.as_str())));
+    if !found {
+        let available = (1..)
+            .zip(parts)
+            .filter(|(_, names)| names.len() > 1)
+            .flat_map(|(part, names)| {
+                names
+                    .iter()
+                    .map(move |n| format!("{}::{n}", PARTS[part - 1]))
+            })
+            .collect::<Vec<_>>();
+        eprintln!(
+            "There is no variant \"{name}\". Available variants: {}.",
+            available.join(", ")
+        );
+        process::exit(1);
+    }
+}
+
+const PARTS: [&str; 2] = ["part_one", "part_two"];
+
+/// The name passed with `--variant` if it applies to `part`, which it does unless it's
+/// qualified with another part, like `part_two::bruteforce`. Part `0` returns it as given.
+fn variant_arg(part: usize) -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let i = args.iter().position(|x| x == "--variant")?;
+    let name = args.get(i + 1).cloned().unwrap_or_else(|| {
+        eprintln!("Unexpected command-line input. Format: cargo solve 5 --variant <name>");
+        process::exit(1);
+    });
+    if part == 0 {
+        return Some(name);
+    }
+    match name.split_once("::") {
+        Some((qualifier, name)) => (qualifier == PARTS[part - 1]).then(|| name.to_string()),
+        None => Some(name),
+    }
+}
+
+/// Runs every variant of a part, checks that they agree and prints how fast they are relative
+/// to the fastest one. Exits with an error if the answers differ.
+fn compare_variants<T: Display>(variants: &Variants<T>, input: &str, part: u8) {
+    let only = env::args().skip_while(|x| x != "--part").nth(1);
+    if only.is_some_and(|only| only != part.to_string()) {
+        return;
+    }
+
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+    let mut rows = vec![];
+    for (name, func) in variants {
+        print!("  {name} > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+
+        viz::suspend(true);
+        let timer = Instant::now();
+        let answer = answer_text(&func(input));
+        let (duration, samples) = sample(func, input, &timer.elapsed(), 1);
+        viz::suspend(false);
+        memo::take_stats();
+
+        print!("\r\x1b[2K");
+        rows.push((*name, answer, duration, samples));
+    }
+
+    let fastest = rows.iter().map(|row| row.2).min().unwrap_or_default();
+    let table = rows
+        .iter()
+        .map(|(name, answer, duration, samples)| {
+            let answer = match answer {
+                Some(answer) if !answer.contains('\n') => answer.clone(),
+                Some(_) => "▼".to_string(),
+                None => "✖".to_string(),
+            };
+            let relative = duration.as_nanos() as f64 / cmp::max(fastest.as_nanos(), 1) as f64;
+            let time = format_duration(duration, *samples);
+            [
+                name.to_string(),
+                answer,
+                format!("{relative:.2}x"),
+                time.trim().to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let width = |column: usize| {
+        table
+            .iter()
+            .map(|row| row[column].chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+    let (name_width, answer_width, relative_width) = (width(0), width(1), width(2));
+    for [name, answer, relative, time] in &table {
+        println!(
+            "  {name:<name_width$}  {ANSI_BOLD}{answer:<answer_width$}{ANSI_RESET}  {relative:>relative_width$}  {ANSI_ITALIC}{time}{ANSI_RESET}"
+        );
+    }
+
+    let disagree = rows.iter().any(|row| row.1 != rows[0].1);
+    if disagree {
+        eprintln!("✖ The variants of part {part} don't agree on the answer.");
+        process::exit(1);
+    }
+    if rows.len() > 1 {
+        println!("  ✔ All variants agree.");
+    }
+}
+
+/// Marks whether `result` matches the recorded answer. Results drawn as block letters are
+/// compared by the text they spell.
+fn check_result<T: Display>(result: &Option<T>, expected: &str) -> String {
+    match answer_text(result).as_deref() == Some(expected) {
+        true => " ✔".to_string(),
+        false => format!(" ✖ {ANSI_ITALIC}expected{ANSI_RESET} {ANSI_BOLD}{expected}{ANSI_RESET}"),
+    }
+}
+
+/// A result as the text it's submitted and compared as.
+fn answer_text<T: Display>(result: &Option<T>) -> Option<String> {
+    result.as_ref().map(|result| {
+        let answer = result.to_string();
+        match answer.contains('\n') {
+            true => ocr::recognize(&answer).unwrap_or(answer),
+            false => answer,
+        }
+    })
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
@@ -30,14 +238,22 @@ fn run_timed<I: Clone, T>(
     input: I,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
+    memo::take_stats();
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
+    if let Some(stats) = memo::take_stats() {
+        info!("memo: {stats}");
+    }
 
     hook(&result);
 
     let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+        viz::suspend(true);
+        let run = bench(func, input, &base_time);
+        viz::suspend(false);
+        memo::take_stats();
+        run
     } else {
         (base_time, 1)
     };
@@ -51,13 +267,18 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    sample(func, input, base_time, 10)
+}
+
+/// Averages approx. 1 second of runs, but at least `min_iterations` and at most 10000.
+fn sample<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    min_iterations: u128,
+) -> (Duration, u128) {
+    let bench_iterations = (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(min_iterations, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -92,22 +313,25 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str, check: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+                let str = format!("{part}: ▼ {duration_str}{check}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
                     print!("\r");
                     println!("{str}");
                     println!("{result}");
+                    if let Ok(text) = ocr::recognize(&result.to_string()) {
+                        println!("{ANSI_ITALIC}reads as{ANSI_RESET} {ANSI_BOLD}{text}{ANSI_RESET}");
+                    }
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}{check}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -121,7 +345,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 print!("{part}: ✖");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: ✖{check}             ");
             }
         }
     }
@@ -162,6 +386,18 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    // answers drawn as block letters are submitted as the text they spell.
+    let mut answer = result.to_string();
+    if answer.contains('\n') {
+        answer = match ocr::recognize(&answer) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("Could not read the letters in the result: {e}");
+                process::exit(1);
+            }
+        };
+    }
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, &answer))
 }
@@ -0,0 +1,527 @@
+//! Visualisations for debugging and sharing solutions.
+//!
+//! Solutions pass anything [`Visualize`] to [`frame`], which does nothing unless the solution
+//! runs with `--viz` or `--viz-out <path>`, so the answer path stays untouched:
+//!
+//! - `--viz` animates the frames in the terminal, waiting `--viz-delay <ms>` (default 100)
+//!   between them.
+//! - `--viz-out frames/` (an existing directory or a path ending in `/`) dumps every frame
+//!   as `frames/00000.ppm`, `frames/00001.ppm`, ...
+//! - `--viz-out anim.gif` assembles the frames into an animated GIF.
+//! - `--viz-out picture.png` (or `.ppm`) keeps the last frame.
+//!
+//! The runner suspends visualisations while benchmarking.
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use crate::bitset::BitGrid;
+
+/// A colour as red, green and blue components.
+pub type Rgb = [u8; 3];
+
+const BACKGROUND: Rgb = [15, 15, 25];
+const FOREGROUND: Rgb = [240, 240, 240];
+const PALETTE: [Rgb; 8] = [
+    [230, 80, 80],
+    [240, 180, 50],
+    [90, 200, 90],
+    [70, 170, 230],
+    [170, 110, 230],
+    [230, 120, 190],
+    [80, 210, 200],
+    [160, 160, 160],
+];
+/// Pixels per cell when grids are turned into images.
+const CELL_PX: usize = 4;
+
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+
+fn arg_value(name: &str) -> Option<String> {
+    let mut args = std::env::args().skip_while(|arg| arg != name);
+    args.nth(1)
+}
+
+fn output_arg() -> &'static Option<PathBuf> {
+    static OUTPUT: OnceLock<Option<PathBuf>> = OnceLock::new();
+    OUTPUT.get_or_init(|| arg_value("--viz-out").map(PathBuf::from))
+}
+
+fn delay() -> Duration {
+    static DELAY: OnceLock<Duration> = OnceLock::new();
+    *DELAY.get_or_init(|| {
+        let ms = arg_value("--viz-delay").and_then(|ms| ms.parse().ok());
+        Duration::from_millis(ms.unwrap_or(100))
+    })
+}
+
+/// Whether the solution should render visualisations.
+pub fn enabled() -> bool {
+    static REQUESTED: OnceLock<bool> = OnceLock::new();
+    let requested =
+        *REQUESTED.get_or_init(|| std::env::args().any(|arg| arg == "--viz" || arg == "--viz-out"));
+    requested && !SUSPENDED.load(Ordering::Relaxed)
+}
+
+/// Turns [`enabled`] off (or back on), e.g. while a solution is benchmarked.
+pub fn suspend(suspended: bool) {
+    SUSPENDED.store(suspended, Ordering::Relaxed);
+}
+
+/// The path passed with `--viz-out`, if any.
+pub fn output_path() -> Option<&'static Path> {
+    output_arg().as_deref()
+}
+
+/// Shows `value` as the next frame of the visualisation. A no-op unless [`enabled`].
+pub fn frame<V: Visualize + ?Sized>(value: &V) {
+    if !enabled() {
+        return;
+    }
+    let mut sink = SINK.lock().unwrap_or_else(|e| e.into_inner());
+    let result = match sink.as_mut() {
+        Some(sink) => sink.push(value),
+        None => Sink::open(output_path()).and_then(|mut new| {
+            let result = new.push(value);
+            *sink = Some(new);
+            result
+        }),
+    };
+    if let Err(e) = result {
+        eprintln!("Visualisation failed, disabling it: {e}");
+        suspend(true);
+    }
+}
+
+/// Completes the visualisation, e.g. writes the end of an animated GIF.
+/// Called by the runner once all parts ran.
+pub fn finish() {
+    let sink = SINK.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(sink) = sink {
+        match sink.finish() {
+            Ok(Some(message)) => println!("{message}"),
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to finish visualisation: {e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Values that can be shown by [`frame`], both in the terminal and as an image.
+pub trait Visualize {
+    /// The frame as (possibly ANSI coloured) text.
+    fn to_text(&self) -> String;
+    fn to_image(&self) -> Image;
+}
+
+fn char_color(c: char) -> Rgb {
+    match c {
+        '.' | ' ' => BACKGROUND,
+        '#' | '█' => FOREGROUND,
+        c => PALETTE[c as usize % PALETTE.len()],
+    }
+}
+
+fn cells_to_image<T: Copy>(rows: &[Vec<T>], color: impl Fn(T) -> Rgb) -> Image {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut image = Image::new(width, rows.len(), BACKGROUND);
+    for (y, row) in rows.iter().enumerate() {
+        for (x, &cell) in row.iter().enumerate() {
+            image.set(x, y, color(cell));
+        }
+    }
+    image.scaled(CELL_PX)
+}
+
+impl Visualize for [Vec<char>] {
+    fn to_text(&self) -> String {
+        self.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn to_image(&self) -> Image {
+        cells_to_image(self, char_color)
+    }
+}
+
+impl Visualize for [Vec<bool>] {
+    fn to_text(&self) -> String {
+        self.iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&b| if b { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn to_image(&self) -> Image {
+        cells_to_image(self, |b| if b { FOREGROUND } else { BACKGROUND })
+    }
+}
+
+impl Visualize for str {
+    fn to_text(&self) -> String {
+        self.to_string()
+    }
+
+    fn to_image(&self) -> Image {
+        let rows = self
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect::<Vec<Vec<char>>>();
+        rows.to_image()
+    }
+}
+
+impl Visualize for BitGrid {
+    fn to_text(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn to_image(&self) -> Image {
+        let mut image = Image::new(self.width(), self.height(), BACKGROUND);
+        for (x, y) in self.iter() {
+            image.set(x, y, FOREGROUND);
+        }
+        image.scaled(CELL_PX)
+    }
+}
+
+impl Visualize for Image {
+    /// Two pixels per character cell, using the upper half block with 24-bit colours.
+    fn to_text(&self) -> String {
+        (0..self.height)
+            .step_by(2)
+            .map(|y| {
+                let mut line = (0..self.width)
+                    .map(|x| {
+                        let [r, g, b] = self.pixels[y * self.width + x];
+                        let [br, bg, bb] = self.get(x, y + 1).unwrap_or(BACKGROUND);
+                        format!("\x1b[38;2;{r};{g};{b}m\x1b[48;2;{br};{bg};{bb}m▀")
+                    })
+                    .collect::<String>();
+                line.push_str("\x1b[0m");
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn to_image(&self) -> Image {
+        self.clone()
+    }
+}
+
+macro_rules! impl_visualize_deref {
+    ($($t:ty),*) => {$(
+        impl Visualize for $t {
+            fn to_text(&self) -> String {
+                (**self).to_text()
+            }
+            fn to_image(&self) -> Image {
+                (**self).to_image()
+            }
+        }
+    )*};
+}
+
+impl_visualize_deref!(Vec<Vec<char>>, Vec<Vec<bool>>, String);
+
+/* -------------------------------------------------------------------------- */
+
+/// Where frames go, chosen by `--viz-out`.
+enum Sink {
+    Terminal,
+    Picture(PathBuf),
+    Frames {
+        dir: PathBuf,
+        count: usize,
+    },
+    Gif {
+        path: PathBuf,
+        encoder: Option<gif::Encoder<BufWriter<File>>>,
+        size: (usize, usize),
+    },
+}
+
+impl Sink {
+    fn open(output: Option<&Path>) -> io::Result<Sink> {
+        let Some(path) = output else {
+            return Ok(Sink::Terminal);
+        };
+        let is_dir = path.is_dir() || path.to_string_lossy().ends_with(['/', '\\']);
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase);
+        Ok(match extension.as_deref() {
+            _ if is_dir => {
+                fs::create_dir_all(path)?;
+                Sink::Frames {
+                    dir: path.to_path_buf(),
+                    count: 0,
+                }
+            }
+            Some("gif") => Sink::Gif {
+                path: path.to_path_buf(),
+                encoder: None,
+                size: (0, 0),
+            },
+            _ => Sink::Picture(path.to_path_buf()),
+        })
+    }
+
+    fn push<V: Visualize + ?Sized>(&mut self, value: &V) -> io::Result<()> {
+        match self {
+            Sink::Terminal => {
+                let mut stdout = io::stdout().lock();
+                // move to the top left corner and clear the screen.
+                writeln!(stdout, "\x1b[H\x1b[2J{}", value.to_text())?;
+                stdout.flush()?;
+                thread::sleep(delay());
+            }
+            Sink::Picture(path) => value.to_image().save(path)?,
+            Sink::Frames { dir, count } => {
+                let path = dir.join(format!("{count:05}.ppm"));
+                value
+                    .to_image()
+                    .write_ppm(BufWriter::new(File::create(path)?))?;
+                *count += 1;
+            }
+            Sink::Gif {
+                path,
+                encoder,
+                size,
+            } => {
+                let image = value.to_image();
+                if encoder.is_none() {
+                    *size = (image.width, image.height);
+                    *encoder = Some(gif_encoder(BufWriter::new(File::create(&*path)?), &image)?);
+                }
+                if (image.width, image.height) != *size {
+                    return Err(io::Error::other("GIF frames must all have the same size"));
+                }
+                if let Some(encoder) = encoder {
+                    encoder
+                        .write_frame(&gif_frame(&image, delay()))
+                        .map_err(io::Error::other)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a message describing what was written, if anything.
+    fn finish(self) -> io::Result<Option<String>> {
+        Ok(match self {
+            Sink::Terminal => None,
+            Sink::Picture(path) => Some(format!("Wrote visualisation to {}", path.display())),
+            Sink::Frames { dir, count } => Some(format!(
+                "Wrote {count} visualisation frames to {}",
+                dir.display()
+            )),
+            Sink::Gif { path, encoder, .. } => {
+                if let Some(encoder) = encoder {
+                    encoder.into_inner()?.flush()?;
+                }
+                Some(format!(
+                    "Wrote animated visualisation to {}",
+                    path.display()
+                ))
+            }
+        })
+    }
+}
+
+fn gif_encoder<W: Write>(writer: W, first: &Image) -> io::Result<gif::Encoder<W>> {
+    let too_large = || io::Error::other("image is too large for a GIF");
+    let width = u16::try_from(first.width).map_err(|_| too_large())?;
+    let height = u16::try_from(first.height).map_err(|_| too_large())?;
+    let mut encoder = gif::Encoder::new(writer, width, height, &[]).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    Ok(encoder)
+}
+
+fn gif_frame(image: &Image, delay: Duration) -> gif::Frame<'static> {
+    let mut frame = gif::Frame::from_rgb_speed(
+        image.width as u16,
+        image.height as u16,
+        &image.pixels.concat(),
+        10,
+    );
+    // GIF delays are in hundredths of a second.
+    frame.delay = (delay.as_millis() / 10).min(u128::from(u16::MAX)) as u16;
+    frame
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An RGB raster image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Sets a pixel, ignoring positions outside the image.
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    /// Fills the `width` x `height` rectangle whose top left corner is `(x, y)`.
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgb) {
+        for py in y..y + height {
+            for px in x..x + width {
+                self.set(px, py, color);
+            }
+        }
+    }
+
+    /// Returns the image with every pixel blown up to a `factor` x `factor` square.
+    pub fn scaled(&self, factor: usize) -> Image {
+        let mut scaled = Image::new(self.width * factor, self.height * factor, [0; 3]);
+        for y in 0..scaled.height {
+            for x in 0..scaled.width {
+                scaled.pixels[y * scaled.width + x] =
+                    self.pixels[(y / factor) * self.width + x / factor];
+            }
+        }
+        scaled
+    }
+
+    /// Writes the image as a binary PPM (`P6`).
+    pub fn write_ppm(&self, mut writer: impl Write) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.pixels.concat())
+    }
+
+    pub fn write_png(&self, writer: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels.concat())?;
+        Ok(())
+    }
+
+    /// Saves the image as PNG if `path` ends in `.png`, as PPM otherwise.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("png") => self.write_png(file),
+            _ => self.write_ppm(file),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_ppm() {
+        let mut image = Image::new(2, 2, [0, 0, 0]);
+        image.set(1, 0, [255, 0, 0]);
+        image.set(5, 5, [1, 1, 1]);
+        assert_eq!(image.get(1, 0), Some([255, 0, 0]));
+        assert_eq!(image.get(2, 0), None);
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([0, 0, 0, 255, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(ppm, expected);
+    }
+
+    #[test]
+    fn writes_png() {
+        let mut image = Image::new(3, 2, [10, 20, 30]);
+        image.fill_rect(1, 1, 5, 5, [200, 100, 0]);
+
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(&buf[..6], &[10, 20, 30, 10, 20, 30]);
+        assert_eq!(&buf[12..], &[200, 100, 0, 200, 100, 0]);
+    }
+
+    #[test]
+    fn grids_to_text_and_images() {
+        let grid = vec![vec!['#', '.'], vec!['.', 'a']];
+        assert_eq!(grid.to_text(), "#.\n.a");
+        let image = grid.to_image();
+        assert_eq!((image.width(), image.height()), (2 * CELL_PX, 2 * CELL_PX));
+        assert_eq!(image.get(CELL_PX - 1, CELL_PX - 1), Some(FOREGROUND));
+        assert_eq!(image.get(CELL_PX, 0), Some(BACKGROUND));
+        assert_eq!("#.\n.a".to_image(), image);
+
+        let mut bits = BitGrid::new(2, 1);
+        bits.insert(1, 0);
+        assert_eq!(bits.to_text(), ".#\n");
+        assert_eq!(vec![vec![false, true]].to_image(), bits.to_image());
+    }
+
+    #[test]
+    fn encodes_gif_frames() {
+        let first = Image::new(4, 3, [255, 0, 0]);
+        let mut encoder = gif_encoder(Vec::new(), &first).unwrap();
+        encoder
+            .write_frame(&gif_frame(&first, Duration::from_millis(250)))
+            .unwrap();
+        encoder
+            .write_frame(&gif_frame(&Image::new(4, 3, [0, 0, 255]), Duration::ZERO))
+            .unwrap();
+        let gif = encoder.into_inner().unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+
+        let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+        let frame = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!((frame.width, frame.height, frame.delay), (4, 3, 25));
+        assert!(decoder.read_next_frame().unwrap().is_some());
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+}